@@ -1,8 +1,8 @@
-use deckofcards::{
-    card, hand, Area, Card, Cards, Color, Deck, Decky, DisplayCard, Hand, Handy, SortCards,
-};
+#![allow(dead_code)]
+
+use deckofcards::game::Table;
+use deckofcards::{Area, Card, Cards, Deck, Decky, DisplayCard, Handy};
 use deref_derive::{Deref, DerefMut};
-use handy_derive::Handy;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum BalatroCard {
@@ -14,7 +14,7 @@ pub enum BalatroCard {
 }
 
 #[derive(Clone, Debug, Default, PartialEq)]
-enum Edition {
+pub enum Edition {
     #[default]
     None,
     Foil,
@@ -24,7 +24,7 @@ enum Edition {
 }
 
 #[derive(Clone, Debug, Default)]
-enum Seal {
+pub enum Seal {
     #[default]
     None,
     Gold,
@@ -34,7 +34,7 @@ enum Seal {
 }
 
 #[derive(Clone, Debug, Default)]
-enum Enhanced {
+pub enum Enhanced {
     #[default]
     None,
     Bonus,
@@ -56,7 +56,7 @@ pub struct Item {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-enum JokerType {
+pub enum JokerType {
     GreenJoker(u32),
 }
 
@@ -68,7 +68,7 @@ pub struct Joker {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-enum TarotType {
+pub enum TarotType {
     Fool,
     Magician,
     Death,
@@ -81,7 +81,7 @@ pub struct Tarot {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-enum PlanetType {
+pub enum PlanetType {
     Mercury,
     Earth,
 }
@@ -93,7 +93,7 @@ pub struct Planet {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-enum SpectralType {
+pub enum SpectralType {
     Familiar,
     Grim,
 }
@@ -105,11 +105,11 @@ pub struct Spectral {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-enum VoucherType {
+pub enum VoucherType {
     Overstock,
     Hone,
-    Clearance_Sale,
-    Reroll_Surplus,
+    ClearanceSale,
+    RerollSurplus,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -129,8 +129,8 @@ impl DisplayCard for BalatroCard {
         }
     }
 
-    fn from_str(s: &str) -> std::prelude::v1::Result<BalatroCard, &'static str> {
-        return Ok(BalatroCard::Joker(Joker {
+    fn from_str(_s: &str) -> core::result::Result<BalatroCard, &'static str> {
+        Ok(BalatroCard::Joker(Joker {
             item: Item {
                 name: "Green Joker".to_string(),
                 shorthand: "GJ".to_string(),
@@ -139,7 +139,7 @@ impl DisplayCard for BalatroCard {
             },
             jtype: JokerType::GreenJoker(0),
             edition: Edition::None,
-        }));
+        }))
     }
 
     #[cfg(feature = "pretty")]
@@ -193,14 +193,12 @@ impl Handy<BalatroCard> for BlatroArea {
     }
 
     fn from_cards(cards: &[BalatroCard]) -> Self {
-        Self {
-            0: Area {
-                cards: Vec::from(cards),
-            },
-        }
+        Self(Area {
+            cards: Vec::from(cards),
+        })
     }
 
-    fn from_strings(card_slice: &[&str]) -> Self {
+    fn from_strings(_card_slice: &[&str]) -> Self {
         todo!()
     }
 
@@ -292,7 +290,7 @@ impl Decky<BalatroCard> for Deck<BalatroCard> {
             .dealt_cards
             .iter()
             .rev()
-            .map(|dc| dc.clone())
+            .cloned()
             .collect::<Vec<BalatroCard>>();
         self.cards.extend(cards);
         self.dealt_cards.clear();
@@ -300,19 +298,25 @@ impl Decky<BalatroCard> for Deck<BalatroCard> {
 }
 
 fn main() {
-    let mut card_deck = Deck::<BalatroCard>::new();
-
-    let mut joker_deck = Deck::<BalatroCard>::empty();
-    let mut voucher_deck = Deck::<BalatroCard>::empty();
-
-    let mut joker_slots = BlatroArea::new();
-    let mut consumeable_slots = BlatroArea::new();
-    let mut hand = BlatroArea::new();
-    let mut playing_hand = BlatroArea::new();
-
-    let mut reroll_shop = BlatroArea::new();
-    let mut pack_shop = BlatroArea::new();
+    let mut table = Table::<BalatroCard, BlatroArea>::new(Deck::<BalatroCard>::new(), &["Player"]);
+    let areas = [
+        "Joker Deck",
+        "Voucher Deck",
+        "Joker Slots",
+        "Consumeable Slots",
+        "Reroll Shop",
+        "Pack Shop",
+    ]
+    .map(|name| table.add_area(name));
+
+    // Deal the starting hand, cards that are played go to the board
+    table.deck.shuffle();
+    table.deal(8);
+    for (name, zone) in table.area_names.iter().zip(areas) {
+        println!("{}: {} cards", name, table.cards(zone).unwrap().len());
+    }
 
     // Put dealt cards back onto the deck
-    card_deck.reset();
+    table.seats[0].hand.clear();
+    table.deck.reset();
 }
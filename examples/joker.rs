@@ -56,6 +56,28 @@ impl DisplayCard for CardWithJoker {
             CardWithJoker::Joker(j) => j.name(),
         }
     }
+
+    fn to_unicode_glyph(&self) -> Option<char> {
+        match self {
+            CardWithJoker::Card(c) => c.to_unicode_glyph(),
+            CardWithJoker::Joker(j) => j.to_unicode_glyph(),
+        }
+    }
+
+    fn from_unicode_glyph(ch: char) -> Result<Self, &'static str>
+    where
+        Self: Sized,
+    {
+        if let Ok(r) = Card::from_unicode_glyph(ch) {
+            return Ok(CardWithJoker::Card(r));
+        }
+
+        if let Ok(r) = Joker::from_unicode_glyph(ch) {
+            return Ok(CardWithJoker::Joker(r));
+        }
+
+        Err("Invalid glyph")
+    }
 }
 
 #[derive(Handy, Clone, Deref, DerefMut)]
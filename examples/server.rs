@@ -0,0 +1,30 @@
+use deckofcards::blackjack::Blackjack;
+use deckofcards::hearts::Hearts;
+use deckofcards::server::{self, Server};
+use deckofcards::solitaire::Klondike;
+use deckofcards::{Card, Cards, Deck, Decky};
+use std::env;
+use std::net::TcpListener;
+
+// Serves a game on localhost. Try it with `nc localhost 7878` and type
+// {"type":"join","name":"Ann"}
+fn main() -> std::io::Result<()> {
+    let game = env::args().nth(1).unwrap_or_else(|| "hearts".to_string());
+    // thread_rng is seeded from the operating system, so every game is dealt differently
+    let seed = rand::random::<u64>();
+    let listener = TcpListener::bind("127.0.0.1:7878")?;
+    println!("Serving {} on {}", game, listener.local_addr()?);
+    match game.as_str() {
+        "hearts" => server::serve(listener, Server::new(Hearts::deal(seed))),
+        "blackjack" => server::serve(listener, Server::new(Blackjack::deal(seed))),
+        "solitaire" => {
+            let mut deck = Deck::<Card>::new();
+            deck.seeded_shuffle(seed);
+            server::serve(listener, Server::new(Klondike::deal(deck)))
+        }
+        _ => {
+            eprintln!("Usage: server [hearts|blackjack|solitaire]");
+            Ok(())
+        }
+    }
+}
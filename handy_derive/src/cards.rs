@@ -0,0 +1,170 @@
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{self, punctuated::Punctuated, Error, Expr, ExprLit, Lit, LitStr, Token};
+
+/// Returns the name of the `Rank` variant for a char, as `Rank::from_char` does
+fn rank_name(c: char) -> Option<&'static str> {
+    let rank = match c {
+        '2' => "Two",
+        '3' => "Three",
+        '4' => "Four",
+        '5' => "Five",
+        '6' => "Six",
+        '7' => "Seven",
+        '8' => "Eight",
+        '9' => "Nine",
+        'T' => "Ten",
+        'J' => "Jack",
+        'Q' => "Queen",
+        'K' => "King",
+        'A' | '1' => "Ace",
+        _ => return None,
+    };
+    Some(rank)
+}
+
+/// Returns the name of the `Suit` variant for a char, as `Suit::from_char` does
+fn suit_name(c: char) -> Option<&'static str> {
+    match c {
+        'S' => Some("Spades"),
+        'H' => Some("Hearts"),
+        'D' => Some("Diamonds"),
+        'C' => Some("Clubs"),
+        _ => None,
+    }
+}
+
+/// Parses a card such as "AS" or "SA" into the names of its rank and suit variants, with the same
+/// rules as `Card::from_str`
+fn parse_card(s: &str) -> Result<(&'static str, &'static str), &'static str> {
+    if s.len() != 2 {
+        return Err("String is wrong length");
+    }
+    let mut chars = s.chars();
+    let (c1, c2) = match (chars.next(), chars.next()) {
+        (Some(c1), Some(c2)) => (c1, c2),
+        _ => return Err("Invalid string"),
+    };
+    // Rank then suit, or suit then rank
+    if let (Some(rank), Some(suit)) = (rank_name(c1), suit_name(c2)) {
+        return Ok((rank, suit));
+    }
+    if let (Some(suit), Some(rank)) = (suit_name(c1), rank_name(c2)) {
+        return Ok((rank, suit));
+    }
+    Err("Invalid string")
+}
+
+/// Splits the input into the path of the deckofcards crate and the rest. The `macro_rules!`
+/// wrappers in deckofcards pass `$crate;` first so the paths work however the crate is named.
+fn split_crate(input: TokenStream2) -> (TokenStream2, TokenStream2) {
+    let tokens = input.into_iter().collect::<Vec<_>>();
+    let semi = tokens
+        .iter()
+        .position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ';'));
+    match semi {
+        Some(i) => (
+            tokens[..i].iter().cloned().collect(),
+            tokens[i + 1..].iter().cloned().collect(),
+        ),
+        None => (quote! { ::deckofcards }, tokens.into_iter().collect()),
+    }
+}
+
+/// Turns a string literal into a const expression for the card, or a compile error at the literal
+fn literal_card(krate: &TokenStream2, lit: &LitStr) -> Result<TokenStream2, Error> {
+    let (rank, suit) = parse_card(&lit.value()).map_err(|reason| {
+        Error::new(
+            lit.span(),
+            format!("Not a known card {:?}: {}", lit.value(), reason),
+        )
+    })?;
+    let rank = format_ident!("{}", rank);
+    let suit = format_ident!("{}", suit);
+    Ok(quote_spanned! {lit.span()=>
+        #krate::Card::new(#krate::Rank::#rank, #krate::Suit::#suit)
+    })
+}
+
+/// Returns the string literal if the expression is one
+fn as_literal(expr: &Expr) -> Option<&LitStr> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Some(lit),
+        _ => None,
+    }
+}
+
+/// Creates the card for a literal at compile time, or for any other expression when it runs
+fn card_expr(krate: &TokenStream2, expr: &Expr) -> Result<TokenStream2, Error> {
+    match as_literal(expr) {
+        Some(lit) => literal_card(krate, lit),
+        None => Ok(quote! {{
+            let s = #expr;
+            <#krate::Card as #krate::DisplayCard>::from_str(s).unwrap_or_else(|_| {
+                panic!("Not a known card {}", s);
+            })
+        }}),
+    }
+}
+
+pub fn card(input: TokenStream) -> TokenStream {
+    let (krate, input) = split_crate(input.into());
+    let expr = match syn::parse2::<Expr>(input) {
+        Ok(expr) => expr,
+        Err(e) => return e.into_compile_error().into(),
+    };
+    card_expr(&krate, &expr)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+pub fn hand(input: TokenStream, unique: bool) -> TokenStream {
+    let (krate, input) = split_crate(input.into());
+    let parser = Punctuated::<Expr, Token![,]>::parse_terminated;
+    let exprs = match syn::parse::Parser::parse2(parser, input) {
+        Ok(exprs) => exprs,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    let mut seen = Vec::new();
+    let mut cards = Vec::new();
+    let mut errors: Option<Error> = None;
+    for expr in &exprs {
+        let result = card_expr(&krate, expr).and_then(|card| {
+            if let Some(lit) = as_literal(expr).filter(|_| unique) {
+                // Cards are compared once parsed, so "AS" and "SA" are the same card
+                let parsed = parse_card(&lit.value()).ok();
+                if seen.contains(&parsed) {
+                    return Err(Error::new(
+                        lit.span(),
+                        format!("Duplicate card {:?} in hand", lit.value()),
+                    ));
+                }
+                seen.push(parsed);
+            }
+            Ok(card)
+        });
+        match result {
+            Ok(card) => cards.push(card),
+            Err(e) => match errors.as_mut() {
+                Some(errors) => errors.combine(e),
+                None => errors = Some(e),
+            },
+        }
+    }
+    if let Some(errors) = errors {
+        return errors.into_compile_error().into();
+    }
+
+    let gen = quote! {{
+        let mut hand = <#krate::Hand as #krate::Handy<#krate::Card>>::new();
+        #(
+            hand += #cards;
+        )*
+        hand
+    }};
+    gen.into()
+}
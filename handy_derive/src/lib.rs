@@ -4,6 +4,7 @@ extern crate proc_macro;
 
 mod handy;
 mod decky;
+mod cards;
 
 #[proc_macro_derive(Handy, attributes(handy_cards))]
 pub fn handy_derive(input: TokenStream) -> TokenStream {
@@ -13,4 +14,22 @@ pub fn handy_derive(input: TokenStream) -> TokenStream {
 #[proc_macro_derive(Decky, attributes(handy_cards))]
 pub fn decky_derive(input: TokenStream) -> TokenStream {
     decky::decky_derive(input)
-}
\ No newline at end of file
+}
+
+/// Creates a `Card` from a string such as "AS". A string literal is checked when compiling.
+#[proc_macro]
+pub fn card(input: TokenStream) -> TokenStream {
+    cards::card(input)
+}
+
+/// Creates a `Hand` from strings such as "AS". String literals are checked when compiling.
+#[proc_macro]
+pub fn hand(input: TokenStream) -> TokenStream {
+    cards::hand(input, false)
+}
+
+/// Creates a `Hand` like `hand!` and also checks when compiling that no card appears twice.
+#[proc_macro]
+pub fn unique_hand(input: TokenStream) -> TokenStream {
+    cards::hand(input, true)
+}
@@ -0,0 +1,107 @@
+/* Generated by cbindgen from src/capi.rs. Do not edit. */
+
+#ifndef DECKOFCARDS_H
+#define DECKOFCARDS_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+// The orders cards can be sorted in, see `SortCards`
+typedef enum SortOrder {
+  SortOrder_SuitAscendingRank,
+  SortOrder_SuitDescendingRank,
+  SortOrder_DescendingRankSuit,
+  SortOrder_AscendingRankSuit,
+} SortOrder;
+
+// An opaque deck of cards
+typedef struct Deck Deck;
+
+// An opaque hand of cards
+typedef struct Hand Hand;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Creates a standard deck of 52 cards
+struct Deck *deckofcards_deck_new(void);
+
+// Creates a deck from the cards, from the bottom to the top. Returns null if a card is not valid.
+struct Deck *deckofcards_deck_from_cards(const uint8_t *cards, size_t len);
+
+// Frees a deck
+void deckofcards_deck_free(struct Deck *deck);
+
+// Shuffles the undealt cards into a random order
+void deckofcards_deck_shuffle(struct Deck *deck);
+
+// Shuffles the undealt cards into the same order as `Cards::seeded_shuffle`
+void deckofcards_deck_seeded_shuffle(struct Deck *deck, uint64_t seed);
+
+// Deals the top card into `card`. Returns false if there are no cards left.
+bool deckofcards_deck_deal_one(struct Deck *deck, uint8_t *card);
+
+// Deals up to `count` cards from the top of the deck into the hand. Returns the number dealt.
+size_t deckofcards_deck_deal_to_hand(struct Deck *deck, struct Hand *hand, size_t count);
+
+// Puts the dealt cards back on top of the deck, so the cards dealt next are the ones dealt before
+// in the same order
+void deckofcards_deck_reset(struct Deck *deck);
+
+// Returns the number of undealt cards
+size_t deckofcards_deck_undealt_count(const struct Deck *deck);
+
+// Returns the number of dealt cards
+size_t deckofcards_deck_dealt_count(const struct Deck *deck);
+
+// Copies up to `capacity` undealt cards, from the bottom to the top, into `out`. Returns the
+// number of undealt cards, which may be more than were copied.
+size_t deckofcards_deck_cards(const struct Deck *deck, uint8_t *out, size_t capacity);
+
+// Creates an empty hand
+struct Hand *deckofcards_hand_new(void);
+
+// Frees a hand
+void deckofcards_hand_free(struct Hand *hand);
+
+// Adds a card to the hand. Returns false if the card is not valid.
+bool deckofcards_hand_push(struct Hand *hand, uint8_t card);
+
+// Removes the first matching card from the hand. Returns false if there is none.
+bool deckofcards_hand_remove(struct Hand *hand, uint8_t card);
+
+// Removes every card from the hand
+void deckofcards_hand_clear(struct Hand *hand);
+
+// Returns the number of cards in the hand
+size_t deckofcards_hand_len(const struct Hand *hand);
+
+// Copies up to `capacity` cards of the hand into `out`. Returns the number of cards in the
+// hand, which may be more than were copied.
+size_t deckofcards_hand_cards(const struct Hand *hand, uint8_t *out, size_t capacity);
+
+// Shuffles the hand into a random order
+void deckofcards_hand_shuffle(struct Hand *hand);
+
+// Shuffles the hand into the same order as `Cards::seeded_shuffle`
+void deckofcards_hand_seeded_shuffle(struct Hand *hand, uint64_t seed);
+
+// Sorts the hand in the order, which is one of the `SortOrder` values. Returns false if the order
+// is not one of them.
+bool deckofcards_hand_sort(struct Hand *hand, uint32_t order);
+
+// Writes the short string of the card, e.g. "AS", and a terminating NUL into `out`, which must
+// have room for 3 bytes. Returns false if the card is not valid.
+bool deckofcards_card_to_str(uint8_t card, char *out);
+
+// Parses a NUL terminated card string such as "AS" or "10h". Returns the ordinal of the card or
+// -1 if it is not valid.
+int32_t deckofcards_card_from_str(const char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* DECKOFCARDS_H */
@@ -0,0 +1,213 @@
+//! Draws cards as boxes of text for terminals.
+//!
+//! Each card is a box with the rank in the top left and bottom right corners and the suit in the
+//! middle. Cards can be drawn face down, and a row of cards overlaps so that only the left edge
+//! and corner of each covered card shows.
+//!
+//! ```text
+//! ┌───┌───┌─────┐
+//! │10 │K  │░░░░░│
+//! │  ♥│  ♠│░░░░░│
+//! │   │   │░░░░░│
+//! └───└───└─────┘
+//! ```
+
+use super::theme::Theme;
+use super::*;
+
+/// The width of a card in columns
+pub const CARD_WIDTH: usize = 7;
+
+/// The height of a card in lines
+pub const CARD_HEIGHT: usize = 5;
+
+/// The characters the edges of a card are drawn with
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Border {
+    /// Box drawing characters, e.g. ┌─┐
+    #[default]
+    Box,
+    /// Plain ASCII for terminals without box drawing characters, e.g. +-+
+    Ascii,
+}
+
+/// What is drawn in one position of a row of cards
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Face {
+    /// A card showing its rank and suit
+    Up(Card),
+    /// A card showing its back
+    Down,
+    /// A joker
+    Joker(Joker),
+}
+
+/// One character of a drawing and the color it is drawn in
+#[derive(Copy, Clone)]
+struct Cell {
+    ch: char,
+    color: Option<colored::Color>,
+}
+
+/// An `Art` draws cards with the chosen border, overlap and colors.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Art {
+    /// The characters the edges are drawn with
+    pub border: Border,
+    /// How many columns of a card show when the next card in a row covers it. The whole card
+    /// shows if this is `CARD_WIDTH` or more.
+    pub visible: usize,
+    /// How ranks and suits are colored
+    pub theme: Theme,
+}
+
+impl Default for Art {
+    fn default() -> Self {
+        Art {
+            border: Border::Box,
+            visible: 4,
+            theme: Theme::current(),
+        }
+    }
+}
+
+impl Art {
+    /// Creates an `Art` which draws without colors
+    pub fn plain() -> Art {
+        Art {
+            theme: Theme::Monochrome,
+            ..Art::default()
+        }
+    }
+
+    /// Draws a card face up
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deckofcards::art::Art;
+    /// use deckofcards::card;
+    ///
+    /// let drawing = Art::plain().card(&card!("TH"));
+    /// assert_eq!(drawing, "┌─────┐\n│10   │\n│  ♥  │\n│   10│\n└─────┘");
+    /// ```
+    pub fn card(&self, card: &Card) -> String {
+        self.faces(&[Face::Up(*card)])
+    }
+
+    /// Draws the back of a card
+    pub fn back(&self) -> String {
+        self.faces(&[Face::Down])
+    }
+
+    /// Draws the cards of a hand face up in a row
+    pub fn hand(&self, hand: &Hand) -> String {
+        let faces = hand
+            .cards()
+            .iter()
+            .map(|c| Face::Up(*c))
+            .collect::<Vec<_>>();
+        self.faces(&faces)
+    }
+
+    /// Draws the cards in a row from left to right, each covering the one before it. Returns an
+    /// empty string if there are no cards.
+    pub fn faces(&self, faces: &[Face]) -> String {
+        let mut rows = vec![Vec::new(); CARD_HEIGHT];
+        for (i, face) in faces.iter().enumerate() {
+            let columns = if i + 1 == faces.len() {
+                CARD_WIDTH
+            } else {
+                self.visible.clamp(1, CARD_WIDTH)
+            };
+            for (row, cells) in rows.iter_mut().zip(self.draw(face)) {
+                row.extend_from_slice(&cells[..columns]);
+            }
+        }
+        if faces.is_empty() {
+            return String::new();
+        }
+        rows.iter()
+            .map(|row| self.line(row))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Draws one card as rows of cells
+    fn draw(&self, face: &Face) -> Vec<Vec<Cell>> {
+        let (top_left, top_right, bottom_left, bottom_right, horizontal, vertical) =
+            match self.border {
+                Border::Box => ('┌', '┐', '└', '┘', '─', '│'),
+                Border::Ascii => ('+', '+', '+', '+', '-', '|'),
+            };
+        let inner = CARD_WIDTH - 2;
+        let edge = |left, right| {
+            let mut row = vec![plain(left)];
+            row.extend(vec![plain(horizontal); inner]);
+            row.push(plain(right));
+            row
+        };
+        let side = |text: Vec<Cell>| {
+            let mut row = vec![plain(vertical)];
+            row.extend(text);
+            row.push(plain(vertical));
+            row
+        };
+
+        let interior = match face {
+            Face::Up(card) => {
+                let color = self.theme.suit_color(card.suit);
+                let rank = match card.rank {
+                    Rank::Ten => "10".to_string(),
+                    rank => rank.to_char().to_string(),
+                };
+                let pip = card.suit.to_unicode().to_string();
+                vec![
+                    text(&format!("{:<inner$}", rank), color),
+                    text(&format!("{:^inner$}", pip), color),
+                    text(&format!("{:>inner$}", rank), color),
+                ]
+            }
+            Face::Joker(joker) => {
+                let color = self.theme.joker_color(joker.color);
+                vec![
+                    text(&format!("{:<inner$}", "JK"), color),
+                    text(&format!("{:^inner$}", "★"), color),
+                    text(&format!("{:>inner$}", "JK"), color),
+                ]
+            }
+            Face::Down => {
+                let back = match self.border {
+                    Border::Box => "░",
+                    Border::Ascii => "#",
+                };
+                vec![text(&back.repeat(inner), None); CARD_HEIGHT - 2]
+            }
+        };
+
+        let mut rows = vec![edge(top_left, top_right)];
+        rows.extend(interior.into_iter().map(side));
+        rows.push(edge(bottom_left, bottom_right));
+        rows
+    }
+
+    /// Turns a row of cells into a line, coloring it if needed
+    fn line(&self, row: &[Cell]) -> String {
+        row.iter()
+            .map(|cell| self.theme.paint(&cell.ch.to_string(), cell.color))
+            .collect()
+    }
+}
+
+fn plain(ch: char) -> Cell {
+    Cell { ch, color: None }
+}
+
+fn text(s: &str, color: Option<colored::Color>) -> Vec<Cell> {
+    s.chars()
+        .map(|ch| Cell {
+            ch,
+            color: color.filter(|_| ch != ' '),
+        })
+        .collect()
+}
@@ -0,0 +1,473 @@
+//! The games the table can host. A `Front` lays a game out as rows of piles and turns the keys
+//! pressed on them into the game's actions.
+
+use deckofcards::art::Face;
+use deckofcards::blackjack::{self, Blackjack, DEALER, PLAYER};
+use deckofcards::game::{Game, Zone};
+use deckofcards::hearts::{self, Hearts};
+use deckofcards::solitaire::{self, Klondike};
+use deckofcards::{Card, Cards, Deck, Decky, Handy, Suit};
+
+/// How the cards of a pile are laid out
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Fan {
+    /// Only the top card shows
+    Stacked,
+    /// Each card is shown below the last, like a tableau pile
+    Down,
+    /// Each card is shown beside the last, like a hand
+    Across,
+}
+
+/// A pile of cards on the screen
+pub struct Pile {
+    /// The title drawn above the pile
+    pub title: String,
+    /// The zone the pile shows, or `None` if it cannot be played to or from
+    pub zone: Option<Zone>,
+    /// The cards from the bottom to the top
+    pub faces: Vec<Face>,
+    /// How the cards are laid out
+    pub fan: Fan,
+}
+
+impl Pile {
+    fn new(title: &str, zone: Option<Zone>, faces: Vec<Face>, fan: Fan) -> Pile {
+        Pile {
+            title: title.to_string(),
+            zone,
+            faces,
+            fan,
+        }
+    }
+}
+
+fn up(cards: &[Card]) -> Vec<Face> {
+    cards.iter().map(|c| Face::Up(*c)).collect()
+}
+
+fn down(count: usize) -> Vec<Face> {
+    vec![Face::Down; count]
+}
+
+/// A game as the table shows and plays it
+pub trait Front {
+    /// The name of the game
+    fn name(&self) -> &'static str;
+
+    /// The piles to draw, row by row
+    fn rows(&self) -> Vec<Vec<Pile>>;
+
+    /// A line about the state of the game, e.g. the score
+    fn status(&self) -> String;
+
+    /// The keys the game understands besides moving and selecting cards
+    fn help(&self) -> &'static str;
+
+    /// Plays the card at the index of the zone, or the zone itself, when nothing is selected
+    fn activate(&mut self, zone: Zone, index: Option<usize>) -> Result<(), &'static str>;
+
+    /// Moves the card at the index of one zone, and any cards on top of it, to another zone
+    fn move_cards(&mut self, from: Zone, index: usize, to: Zone) -> Result<(), &'static str>;
+
+    /// Handles a key for the game, returning `None` if it has no meaning
+    fn key(&mut self, _key: char) -> Option<Result<(), &'static str>> {
+        None
+    }
+
+    /// Starts a new game of the same kind
+    fn restart(&self, seed: u64) -> Box<dyn Front>;
+
+    /// Copies the game so the move can be undone
+    fn boxed_clone(&self) -> Box<dyn Front>;
+}
+
+/// Klondike patience
+#[derive(Clone)]
+pub struct Solitaire(Game<Klondike>);
+
+impl Solitaire {
+    pub fn new(seed: u64) -> Solitaire {
+        let mut deck = Deck::new();
+        deck.seeded_shuffle(seed);
+        Solitaire(Klondike::deal(deck))
+    }
+}
+
+impl Front for Solitaire {
+    fn name(&self) -> &'static str {
+        "Solitaire"
+    }
+
+    fn rows(&self) -> Vec<Vec<Pile>> {
+        let table = &self.0.table;
+        let pile = |title: &str, zone: Zone| {
+            let cards = table.cards(zone).unwrap();
+            let face_down = self.0.rules.face_down(zone);
+            let mut faces = down(face_down);
+            faces.extend(up(&cards[face_down..]));
+            let fan = if solitaire::TABLEAU.contains(&zone) {
+                Fan::Down
+            } else {
+                Fan::Stacked
+            };
+            Pile::new(title, Some(zone), faces, fan)
+        };
+        let mut top = vec![
+            Pile::new(
+                "Stock",
+                Some(Zone::Stock),
+                down(table.deck.undealt_count()),
+                Fan::Stacked,
+            ),
+            pile("Waste", solitaire::WASTE),
+        ];
+        for (i, zone) in solitaire::FOUNDATIONS.iter().enumerate() {
+            top.push(pile(&format!("Found. {}", i + 1), *zone));
+        }
+        let tableau = solitaire::TABLEAU
+            .iter()
+            .enumerate()
+            .map(|(i, zone)| pile(&format!("Pile {}", i + 1), *zone))
+            .collect();
+        vec![top, tableau]
+    }
+
+    fn status(&self) -> String {
+        if self.0.is_over() {
+            return "Every card is home, you won!".to_string();
+        }
+        let home = solitaire::FOUNDATIONS
+            .iter()
+            .map(|f| self.0.table.cards(*f).unwrap().len())
+            .sum::<usize>();
+        format!("{} of 52 cards on the foundations", home)
+    }
+
+    fn help(&self) -> &'static str {
+        "enter on the stock draws, enter on a card sends it to a foundation"
+    }
+
+    fn activate(&mut self, zone: Zone, index: Option<usize>) -> Result<(), &'static str> {
+        if zone == Zone::Stock {
+            return self.0.act(0, &solitaire::Action::Draw);
+        }
+        let cards = self.0.table.cards(zone).ok_or("No such pile")?;
+        let (index, card) = match index {
+            Some(index) if index + 1 == cards.len() => (index, cards[index]),
+            _ => return Err("Select cards with space, then press enter where they go"),
+        };
+        let to = Klondike::foundation_for(&self.0.table, &card)
+            .ok_or("Card does not build on a foundation")?;
+        self.move_cards(zone, index, to)
+    }
+
+    fn move_cards(&mut self, from: Zone, index: usize, to: Zone) -> Result<(), &'static str> {
+        self.0.act(0, &solitaire::Action::Move { from, index, to })
+    }
+
+    fn restart(&self, seed: u64) -> Box<dyn Front> {
+        Box::new(Solitaire::new(seed))
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Front> {
+        Box::new(self.clone())
+    }
+}
+
+/// Blackjack against the dealer
+#[derive(Clone)]
+pub struct BlackjackTable(Game<Blackjack>);
+
+impl BlackjackTable {
+    pub fn new(seed: u64) -> BlackjackTable {
+        BlackjackTable(Blackjack::deal(seed))
+    }
+
+    fn act(&mut self, action: blackjack::Action) -> Result<(), &'static str> {
+        self.0.act(PLAYER, &action)
+    }
+}
+
+impl Front for BlackjackTable {
+    fn name(&self) -> &'static str {
+        "Blackjack"
+    }
+
+    fn rows(&self) -> Vec<Vec<Pile>> {
+        let table = &self.0.table;
+        let playing = self.0.turn().phase == blackjack::Phase::Playing;
+        let dealer = table.seats[DEALER].hand.cards();
+        let mut dealer_faces = up(dealer);
+        if playing && dealer_faces.len() > 1 {
+            // The hole card stays face down until the player stands
+            dealer_faces[1] = Face::Down;
+        }
+        vec![
+            vec![Pile::new(
+                "Dealer",
+                Some(Zone::Hand(DEALER)),
+                dealer_faces,
+                Fan::Across,
+            )],
+            vec![Pile::new(
+                "You",
+                Some(Zone::Hand(PLAYER)),
+                up(table.seats[PLAYER].hand.cards()),
+                Fan::Across,
+            )],
+            vec![
+                Pile::new(
+                    "Shoe",
+                    Some(Zone::Stock),
+                    down(table.deck.undealt_count()),
+                    Fan::Stacked,
+                ),
+                Pile::new(
+                    "Discards",
+                    Some(Zone::Discard),
+                    down(table.cards(Zone::Discard).unwrap().len()),
+                    Fan::Stacked,
+                ),
+            ],
+        ]
+    }
+
+    fn status(&self) -> String {
+        let rules = &self.0.rules;
+        let (player, _) = blackjack::total(self.0.table.seats[PLAYER].hand.cards());
+        let round = match rules.outcome {
+            None => format!("You have {}", player),
+            Some(outcome) => {
+                let (dealer, _) = blackjack::total(self.0.table.seats[DEALER].hand.cards());
+                format!(
+                    "You have {}, the dealer has {}: {:?}",
+                    player, dealer, outcome
+                )
+            }
+        };
+        format!(
+            "{}. Won {}, lost {}, pushed {}",
+            round, rules.wins, rules.losses, rules.pushes
+        )
+    }
+
+    fn help(&self) -> &'static str {
+        "h hit, s stand, d deal"
+    }
+
+    fn activate(&mut self, zone: Zone, _index: Option<usize>) -> Result<(), &'static str> {
+        match zone {
+            Zone::Stock => self.act(blackjack::Action::Hit),
+            Zone::Hand(PLAYER) => self.act(blackjack::Action::Stand),
+            _ => Err("Press h to hit or s to stand"),
+        }
+    }
+
+    fn move_cards(&mut self, _from: Zone, _index: usize, _to: Zone) -> Result<(), &'static str> {
+        Err("Cards are not moved in blackjack")
+    }
+
+    fn key(&mut self, key: char) -> Option<Result<(), &'static str>> {
+        let action = match key {
+            'h' => blackjack::Action::Hit,
+            's' => blackjack::Action::Stand,
+            'd' => blackjack::Action::Deal,
+            _ => return None,
+        };
+        Some(self.act(action))
+    }
+
+    fn restart(&self, seed: u64) -> Box<dyn Front> {
+        Box::new(BlackjackTable::new(seed))
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Front> {
+        Box::new(self.clone())
+    }
+}
+
+/// Hearts with three computer players, and why they stopped playing if the rules refused one
+/// of their plays
+#[derive(Clone)]
+pub struct HeartsTable(Game<Hearts>, Option<&'static str>);
+
+/// The seat of the person at the keyboard
+const YOU: usize = 0;
+
+impl HeartsTable {
+    pub fn new(seed: u64) -> HeartsTable {
+        let mut front = HeartsTable(Hearts::deal(seed), None);
+        front.play_bots();
+        front
+    }
+
+    /// Returns your cards sorted by suit and rank, in the order they are shown
+    fn your_cards(&self) -> Vec<Card> {
+        let mut cards = self.0.table.seats[YOU].hand.cards().to_vec();
+        cards.sort_by(|a, b| a.cmp_suit_then_rank(b));
+        cards
+    }
+
+    fn play(&mut self, index: usize) -> Result<(), &'static str> {
+        let card = *self.your_cards().get(index).ok_or("No such card")?;
+        self.0.act(YOU, &hearts::Action::Play(card))?;
+        self.play_bots();
+        Ok(())
+    }
+
+    /// Lets the computer players take their turns until it is yours again. Stops at the first
+    /// play the rules refuse, leaving it that player's turn and the reason in the status, so
+    /// your own move still stands.
+    fn play_bots(&mut self) {
+        self.1 = self.try_play_bots().err();
+    }
+
+    fn try_play_bots(&mut self) -> Result<(), &'static str> {
+        while !self.0.is_over() && self.0.turn().seat != YOU {
+            let seat = self.0.turn().seat;
+            let action = match self.0.turn().phase {
+                hearts::Phase::HandOver => hearts::Action::Deal,
+                hearts::Phase::Play => hearts::Action::Play(bot_play(&self.0, seat)?),
+            };
+            self.0.act(seat, &action)?;
+        }
+        Ok(())
+    }
+}
+
+/// Chooses a card for a computer player: duck under the winning card when following, win a
+/// trick without points when last, and throw away the Queen of Spades and high Hearts when void
+fn bot_play(game: &Game<Hearts>, seat: usize) -> Result<Card, &'static str> {
+    let rules = &game.rules;
+    let mut plays = rules.legal_plays(&game.table, seat);
+    plays.sort_by(|a, b| a.cmp_rank_then_suit(b));
+    let lowest = *plays.first().ok_or("No card to play")?;
+    let highest = *plays.last().ok_or("No card to play")?;
+    let (_, led) = match rules.trick.first() {
+        None => return Ok(lowest),
+        Some(first) => *first,
+    };
+    if lowest.suit == led.suit {
+        let winning = rules
+            .trick
+            .iter()
+            .filter(|(_, c)| c.suit == led.suit)
+            .map(|(_, c)| c.rank)
+            .max()
+            .unwrap();
+        let no_points = rules.trick.iter().all(|(_, c)| hearts::points(c) == 0);
+        if rules.trick.len() == hearts::SEATS - 1 && no_points {
+            return Ok(highest);
+        }
+        return Ok(plays
+            .iter()
+            .rfind(|c| c.rank < winning)
+            .copied()
+            .unwrap_or(lowest));
+    }
+    let queen = plays.iter().find(|c| hearts::points(c) == 13);
+    let heart = plays.iter().rfind(|c| c.suit == Suit::Hearts);
+    Ok(*queen.or(heart).unwrap_or(&highest))
+}
+
+impl Front for HeartsTable {
+    fn name(&self) -> &'static str {
+        "Hearts"
+    }
+
+    fn rows(&self) -> Vec<Vec<Pile>> {
+        let table = &self.0.table;
+        let rules = &self.0.rules;
+        let seat = |i: usize, fan| {
+            let name = &table.seats[i].name;
+            let faces = down(table.seats[i].hand.len());
+            Pile::new(name, Some(Zone::Hand(i)), faces, fan)
+        };
+        let trick = rules.trick.iter().map(|(_, c)| *c).collect::<Vec<_>>();
+        let last = rules.last_trick.iter().map(|(_, c)| *c).collect::<Vec<_>>();
+        vec![
+            vec![seat(2, Fan::Across)],
+            vec![
+                seat(1, Fan::Stacked),
+                Pile::new("Trick", Some(Zone::Board), up(&trick), Fan::Across),
+                seat(3, Fan::Stacked),
+                Pile::new("Last trick", None, up(&last), Fan::Across),
+            ],
+            vec![Pile::new(
+                "You",
+                Some(Zone::Hand(YOU)),
+                up(&self.your_cards()),
+                Fan::Across,
+            )],
+        ]
+    }
+
+    fn status(&self) -> String {
+        let table = &self.0.table;
+        let rules = &self.0.rules;
+        let taken = Hearts::hand_points(table);
+        let scores = (0..hearts::SEATS)
+            .map(|s| {
+                format!(
+                    "{} {} (+{})",
+                    table.seats[s].name, rules.scores[s], taken[s]
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let state = if let Some(error) = self.1 {
+            let name = &table.seats[self.0.turn().seat].name;
+            format!("{} cannot play: {}", name, error)
+        } else if self.0.is_over() {
+            let winners = rules
+                .leaders()
+                .iter()
+                .map(|s| table.seats[*s].name.as_str())
+                .collect::<Vec<_>>();
+            format!("Game over, {} won", winners.join(" and "))
+        } else if self.0.turn().phase == hearts::Phase::HandOver {
+            "Hand over, press d to deal".to_string()
+        } else {
+            "Your turn".to_string()
+        };
+        format!("{}. {}", state, scores)
+    }
+
+    fn help(&self) -> &'static str {
+        "enter on a card plays it, d deals the next hand"
+    }
+
+    fn activate(&mut self, zone: Zone, index: Option<usize>) -> Result<(), &'static str> {
+        match (zone, index) {
+            (Zone::Hand(YOU), Some(index)) => self.play(index),
+            _ => Err("Press enter on one of your cards to play it"),
+        }
+    }
+
+    fn move_cards(&mut self, from: Zone, index: usize, to: Zone) -> Result<(), &'static str> {
+        match (from, to) {
+            (Zone::Hand(YOU), Zone::Board) => self.play(index),
+            _ => Err("Cards can only be played from your hand to the trick"),
+        }
+    }
+
+    fn key(&mut self, key: char) -> Option<Result<(), &'static str>> {
+        if key != 'd' {
+            return None;
+        }
+        let result = self.0.act(YOU, &hearts::Action::Deal);
+        if result.is_ok() {
+            self.play_bots();
+        }
+        Some(result)
+    }
+
+    fn restart(&self, seed: u64) -> Box<dyn Front> {
+        Box::new(HeartsTable::new(seed))
+    }
+
+    fn boxed_clone(&self) -> Box<dyn Front> {
+        Box::new(self.clone())
+    }
+}
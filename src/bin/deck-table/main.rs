@@ -0,0 +1,498 @@
+//! `deck-table` is a terminal card table for playing the crate's games: Klondike solitaire,
+//! blackjack and hearts. Cards are picked with the arrow keys and space and moved to another pile
+//! with enter, and every move can be undone.
+
+mod fronts;
+
+use std::env;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use deckofcards::art::Face;
+use deckofcards::history::{History, Undoable};
+use deckofcards::theme::Theme;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use fronts::{BlackjackTable, Fan, Front, HeartsTable, Pile, Solitaire};
+
+const KEYS: &str =
+    "arrows move, space selects, enter plays or moves, esc clears, u undo, r redo, n new game, \
+1-3 switch game, q quit";
+
+/// A game being played
+struct Table(Box<dyn Front>);
+
+impl Clone for Table {
+    fn clone(&self) -> Self {
+        Table(self.0.boxed_clone())
+    }
+}
+
+/// A move, kept as the game before and after it
+#[derive(Clone)]
+struct Move {
+    before: Table,
+    after: Table,
+}
+
+impl Undoable for Table {
+    type Event = Move;
+
+    fn apply(&mut self, event: &Move) {
+        *self = event.after.clone();
+    }
+
+    fn revert(&mut self, event: &Move) {
+        *self = event.before.clone();
+    }
+}
+
+/// A game being played with its undo and redo history
+type Slot = History<Table>;
+
+/// The card under the cursor, as the row and column of its pile and its index in the pile
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+struct Spot {
+    row: usize,
+    col: usize,
+    index: Option<usize>,
+}
+
+struct App {
+    slots: Vec<Slot>,
+    current: usize,
+    cursor: Spot,
+    selected: Option<Spot>,
+    message: Option<String>,
+    seed: u64,
+}
+
+impl App {
+    fn new(seed: u64) -> App {
+        let fronts: Vec<Box<dyn Front>> = vec![
+            Box::new(Solitaire::new(seed)),
+            Box::new(BlackjackTable::new(seed)),
+            Box::new(HeartsTable::new(seed)),
+        ];
+        let mut app = App {
+            slots: fronts
+                .into_iter()
+                .map(|front| History::new(Table(front)))
+                .collect(),
+            current: 0,
+            cursor: Spot::default(),
+            selected: None,
+            message: None,
+            seed,
+        };
+        app.clamp_cursor();
+        app
+    }
+
+    fn slot(&mut self) -> &mut Slot {
+        &mut self.slots[self.current]
+    }
+
+    fn front(&self) -> &dyn Front {
+        self.slots[self.current].0.as_ref()
+    }
+
+    /// Makes a move, keeping the game as it was so the move can be undone
+    fn play<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut dyn Front) -> Result<(), &'static str>,
+    {
+        let before = Table::clone(self.slot());
+        let mut after = before.clone();
+        match f(after.0.as_mut()) {
+            Ok(()) => {
+                self.slot().record(Move { before, after });
+                self.message = None;
+            }
+            Err(e) => self.message = Some(e.to_string()),
+        }
+        self.selected = None;
+        self.clamp_cursor();
+    }
+
+    fn undo(&mut self) {
+        if !self.slot().undo() {
+            self.message = Some("Nothing to undo".to_string());
+        }
+        self.selected = None;
+        self.clamp_cursor();
+    }
+
+    fn redo(&mut self) {
+        if !self.slot().redo() {
+            self.message = Some("Nothing to redo".to_string());
+        }
+        self.selected = None;
+        self.clamp_cursor();
+    }
+
+    /// Deals a new game in place of the current one, which can be undone like a move
+    fn new_game(&mut self) {
+        self.seed = self.seed.wrapping_add(1);
+        let seed = self.seed;
+        let before = Table::clone(self.slot());
+        let after = Table(before.0.restart(seed));
+        self.slot().record(Move { before, after });
+        self.message = None;
+        self.selected = None;
+        self.clamp_cursor();
+    }
+
+    fn switch(&mut self, game: usize) {
+        if game < self.slots.len() {
+            self.current = game;
+            self.cursor = Spot::default();
+            self.selected = None;
+            self.message = None;
+            self.clamp_cursor();
+        }
+    }
+
+    /// Returns the pile under a spot
+    fn pile_at(rows: &[Vec<Pile>], spot: Spot) -> Option<&Pile> {
+        rows.get(spot.row).and_then(|row| row.get(spot.col))
+    }
+
+    /// Keeps the cursor on a pile that exists and on a card in it
+    fn clamp_cursor(&mut self) {
+        let rows = self.front().rows();
+        let cursor = &mut self.cursor;
+        cursor.row = cursor.row.min(rows.len().saturating_sub(1));
+        let row = &rows[cursor.row];
+        cursor.col = cursor.col.min(row.len().saturating_sub(1));
+        let len = row.get(cursor.col).map_or(0, |p| p.faces.len());
+        cursor.index = match (cursor.index, row.get(cursor.col).map(|p| p.fan)) {
+            _ if len == 0 => None,
+            (Some(index), Some(Fan::Down | Fan::Across)) => Some(index.min(len - 1)),
+            _ => Some(len - 1),
+        };
+    }
+
+    /// Moves the cursor across a row, through the cards of a hand before moving to the next pile
+    fn move_across(&mut self, right: bool) {
+        let rows = self.front().rows();
+        let pile = App::pile_at(&rows, self.cursor);
+        if let (Some(pile), Some(index)) = (pile, self.cursor.index) {
+            if pile.fan == Fan::Across {
+                if right && index + 1 < pile.faces.len() {
+                    self.cursor.index = Some(index + 1);
+                    return;
+                } else if !right && index > 0 {
+                    self.cursor.index = Some(index - 1);
+                    return;
+                }
+            }
+        }
+        let row = &rows[self.cursor.row];
+        let col = match right {
+            true if self.cursor.col + 1 < row.len() => self.cursor.col + 1,
+            false if self.cursor.col > 0 => self.cursor.col - 1,
+            _ => return,
+        };
+        self.cursor.col = col;
+        // Enter a hand from the side the cursor came from
+        self.cursor.index = match row[col].fan {
+            Fan::Across if right => Some(0),
+            _ => None,
+        };
+        self.clamp_cursor();
+    }
+
+    /// Moves the cursor up or down a tableau pile before moving to the next row
+    fn move_down(&mut self, down: bool) {
+        let rows = self.front().rows();
+        let pile = App::pile_at(&rows, self.cursor);
+        if let (Some(pile), Some(index)) = (pile, self.cursor.index) {
+            if pile.fan == Fan::Down {
+                if down && index + 1 < pile.faces.len() {
+                    self.cursor.index = Some(index + 1);
+                    return;
+                } else if !down && index > 0 && pile.faces[index - 1] != Face::Down {
+                    self.cursor.index = Some(index - 1);
+                    return;
+                }
+            }
+        }
+        self.cursor.row = match down {
+            true if self.cursor.row + 1 < rows.len() => self.cursor.row + 1,
+            false if self.cursor.row > 0 => self.cursor.row - 1,
+            _ => return,
+        };
+        self.cursor.index = None;
+        self.clamp_cursor();
+    }
+
+    fn select(&mut self) {
+        let rows = self.front().rows();
+        let pile = App::pile_at(&rows, self.cursor);
+        self.selected = match (pile, self.cursor.index) {
+            _ if self.selected == Some(self.cursor) => None,
+            (Some(pile), Some(index)) if pile.zone.is_some() && pile.faces[index] != Face::Down => {
+                Some(self.cursor)
+            }
+            _ => {
+                self.message = Some("There is no card to select there".to_string());
+                None
+            }
+        };
+    }
+
+    fn enter(&mut self) {
+        let rows = self.front().rows();
+        let to = match App::pile_at(&rows, self.cursor).and_then(|p| p.zone) {
+            Some(zone) => zone,
+            None => {
+                self.message = Some("Nothing can be played there".to_string());
+                return;
+            }
+        };
+        match self.selected {
+            Some(from) => {
+                let zone = App::pile_at(&rows, from).and_then(|p| p.zone).unwrap();
+                let index = from.index.unwrap();
+                self.play(|front| front.move_cards(zone, index, to));
+            }
+            None => {
+                let index = self.cursor.index;
+                self.play(|front| front.activate(to, index));
+            }
+        }
+    }
+
+    /// Handles a key press, returning false to quit
+    fn key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char('q') => return false,
+            KeyCode::Left => self.move_across(false),
+            KeyCode::Right => self.move_across(true),
+            KeyCode::Up => self.move_down(false),
+            KeyCode::Down => self.move_down(true),
+            KeyCode::Char(' ') => self.select(),
+            KeyCode::Enter => self.enter(),
+            KeyCode::Esc => self.selected = None,
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Char('r') => self.redo(),
+            KeyCode::Char('n') => self.new_game(),
+            KeyCode::Char(c @ '1'..='9') => self.switch(c as usize - '1' as usize),
+            KeyCode::Char(c) => self.play(|front| front.key(c).unwrap_or(Err("Unknown key"))),
+            _ => {}
+        }
+        true
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let rows = self.front().rows();
+        let mut constraints = vec![Constraint::Length(1)];
+        constraints.extend(rows.iter().map(|row| Constraint::Length(row_height(row))));
+        constraints.extend([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ]);
+        let areas = Layout::vertical(constraints).split(frame.area());
+
+        let tabs = self
+            .slots
+            .iter()
+            .enumerate()
+            .map(|(i, slot)| {
+                let style = if i == self.current {
+                    Style::new().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::new()
+                };
+                Span::styled(format!(" {} {} ", i + 1, slot.0.name()), style)
+            })
+            .collect::<Vec<_>>();
+        frame.render_widget(Line::from(tabs), areas[0]);
+
+        for (r, row) in rows.iter().enumerate() {
+            let widths = row.iter().map(|p| Constraint::Length(pile_width(p)));
+            let cells = Layout::horizontal(widths).spacing(1).split(areas[r + 1]);
+            for (c, pile) in row.iter().enumerate() {
+                self.draw_pile(frame, cells[c], pile, r, c);
+            }
+        }
+
+        let n = areas.len();
+        frame.render_widget(Line::from(self.front().status()), areas[n - 3]);
+        let message = self.message.clone().unwrap_or_default();
+        frame.render_widget(
+            Line::styled(message, Style::new().fg(Color::Yellow)),
+            areas[n - 2],
+        );
+        let help = format!("{}. {}", self.front().help(), KEYS);
+        frame.render_widget(
+            Line::styled(help, Style::new().add_modifier(Modifier::DIM)),
+            areas[n - 1],
+        );
+    }
+
+    fn draw_pile(&self, frame: &mut Frame, area: Rect, pile: &Pile, row: usize, col: usize) {
+        let here = |spot: Spot| spot.row == row && spot.col == col;
+        let cursor = here(self.cursor).then_some(self.cursor.index).flatten();
+        let selected = self
+            .selected
+            .filter(|s| here(*s))
+            .and_then(|s| s.index)
+            .map(|index| (index, pile.fan == Fan::Down));
+
+        let style = |i: usize| {
+            let mut style = Style::new();
+            match selected {
+                // A selected tableau card takes the cards on top of it with it
+                Some((index, true)) if i >= index => style = style.bg(Color::Yellow),
+                Some((index, _)) if i == index => style = style.bg(Color::Yellow),
+                _ => {}
+            }
+            if cursor == Some(i) {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            style
+        };
+
+        let lines = match pile.fan {
+            Fan::Stacked => {
+                let top = pile.faces.len().checked_sub(1);
+                let spans = top.map_or(vec![Span::raw("  ")], |i| face(&pile.faces[i], style(i)));
+                vec![Line::from(spans)]
+            }
+            Fan::Down => pile
+                .faces
+                .iter()
+                .enumerate()
+                .map(|(i, f)| Line::from(face(f, style(i))))
+                .collect(),
+            Fan::Across => {
+                let mut spans = Vec::new();
+                for (i, f) in pile.faces.iter().enumerate() {
+                    if i > 0 {
+                        spans.push(Span::raw(" "));
+                    }
+                    spans.extend(face(f, style(i)));
+                }
+                vec![Line::from(spans)]
+            }
+        };
+
+        let title = match pile.fan {
+            Fan::Stacked if !pile.faces.is_empty() => {
+                format!("{} ({})", pile.title, pile.faces.len())
+            }
+            _ => pile.title.clone(),
+        };
+        let mut block = Block::bordered().title(title);
+        if here(self.cursor) && pile.faces.is_empty() {
+            block = block.border_style(Style::new().add_modifier(Modifier::REVERSED));
+        }
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+/// Returns the spans for a card, with the suit colored by the current theme
+fn face(face: &Face, style: Style) -> Vec<Span<'static>> {
+    match face {
+        Face::Up(card) => {
+            let theme = Theme::current();
+            let mut suit = style;
+            if let Some(color) = theme.suit_color(card.suit) {
+                suit = suit.fg(to_color(color));
+                if theme == Theme::HighContrast {
+                    suit = suit.add_modifier(Modifier::BOLD);
+                }
+            }
+            vec![
+                Span::styled(card.rank.to_char().to_string(), style),
+                Span::styled(card.suit.to_unicode().to_string(), suit),
+            ]
+        }
+        Face::Down => vec![Span::styled("░░", style.fg(Color::Blue))],
+        Face::Joker(_) => vec![Span::styled("JK", style)],
+    }
+}
+
+fn to_color(color: colored::Color) -> Color {
+    use colored::Color as C;
+    match color {
+        C::Black => Color::Black,
+        C::Red => Color::Red,
+        C::Green => Color::Green,
+        C::Yellow => Color::Yellow,
+        C::Blue => Color::Blue,
+        C::Magenta => Color::Magenta,
+        C::Cyan => Color::Cyan,
+        C::White => Color::Gray,
+        C::BrightBlack => Color::DarkGray,
+        C::BrightRed => Color::LightRed,
+        C::BrightGreen => Color::LightGreen,
+        C::BrightYellow => Color::LightYellow,
+        C::BrightBlue => Color::LightBlue,
+        C::BrightMagenta => Color::LightMagenta,
+        C::BrightCyan => Color::LightCyan,
+        C::BrightWhite => Color::White,
+        C::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+    }
+}
+
+fn row_height(row: &[Pile]) -> u16 {
+    let cards = |p: &Pile| match p.fan {
+        Fan::Down => p.faces.len().max(1),
+        _ => 1,
+    };
+    row.iter().map(cards).max().unwrap_or(1) as u16 + 2
+}
+
+fn pile_width(pile: &Pile) -> u16 {
+    let title = pile.title.chars().count() + 7;
+    let cards = match pile.fan {
+        Fan::Across => pile.faces.len() * 3,
+        _ => 2,
+    };
+    title.max(cards + 2) as u16
+}
+
+fn run(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+    loop {
+        terminal.draw(|frame| app.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !app.key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn main() -> io::Result<()> {
+    let mut args = env::args().skip(1);
+    let seed = match (args.next().as_deref(), args.next()) {
+        (Some("--seed"), Some(seed)) => seed.parse().ok(),
+        (None, _) => SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_nanos() as u64),
+        _ => None,
+    };
+    let seed = match seed {
+        Some(seed) => seed,
+        None => {
+            eprintln!("Usage: deck-table [--seed N]\n\n{}", KEYS);
+            std::process::exit(2);
+        }
+    };
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, App::new(seed));
+    ratatui::restore();
+    result
+}
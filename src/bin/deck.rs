@@ -0,0 +1,290 @@
+//! The `deck` command shuffles and deals decks, and parses, converts and evaluates cards from the
+//! shell. Every command can write JSON instead of text with `--json`.
+
+use std::env;
+use std::fmt::Write;
+use std::io::{self, IsTerminal};
+use std::process;
+
+use serde_json::{json, Value};
+
+use deckofcards::locale::Locale;
+use deckofcards::notation::{self, Strictness};
+use deckofcards::{poker, Card, Cards, Deck, Decky, DisplayCard, Hand, Handy};
+
+const USAGE: &str = "Usage: deck [--json] [--seed N] [--locale CODE] COMMAND [ARGS]
+
+Commands:
+  shuffle               Prints a shuffled deck in the order it deals
+  deal HANDS CARDS      Deals HANDS hands of CARDS cards each from a shuffled deck
+  parse CARD...         Prints each card in every notation
+  convert FORMAT CARD...
+                        Converts cards to FORMAT, one of short, pretty, name, glyph or ordinal
+  eval CARD...          Evaluates a poker hand of five to seven cards
+
+Cards may be written in any notation, e.g. AS, 10h, A♠, \"Ace of Spades\" or 🂡, and several cards
+may be given in one argument separated by spaces or commas. With --locale, names and short forms
+are translated, e.g. --locale fr.";
+
+/// The options that apply to every command
+struct Options {
+    json: bool,
+    seed: Option<u64>,
+    locale: Locale,
+}
+
+fn main() {
+    // Colors are only for people, so output to a pipe or file is left plain
+    #[cfg(feature = "pretty")]
+    if !io::stdout().is_terminal() {
+        deckofcards::theme::Theme::set(deckofcards::theme::Theme::Monochrome);
+    }
+
+    let mut options = Options {
+        json: false,
+        seed: None,
+        locale: Locale::English,
+    };
+    let mut args = Vec::new();
+    let mut all = env::args().skip(1);
+    while let Some(arg) = all.next() {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--seed" => {
+                let seed = all.next().and_then(|s| s.parse().ok());
+                options.seed = Some(seed.unwrap_or_else(|| usage("--seed needs a number")));
+            }
+            "--locale" => {
+                let code = all.next().unwrap_or_default();
+                options.locale = Locale::from_code(&code).unwrap_or_else(|e| usage(e));
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => args.push(arg),
+        }
+    }
+
+    let (command, args) = match args.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        None => usage("Missing command"),
+    };
+    let output = match command {
+        "shuffle" => shuffle(&options),
+        "deal" => deal(&options, args),
+        "parse" => parse(&options, args),
+        "convert" => convert(&options, args),
+        "eval" => eval(&options, args),
+        _ => usage("Unknown command"),
+    };
+    match output {
+        Ok(output) => println!("{}", output),
+        Err(error) => {
+            if options.json {
+                println!("{}", json!({ "error": error }));
+            } else {
+                eprintln!("deck: {}", error);
+            }
+            process::exit(1);
+        }
+    }
+}
+
+/// Prints the usage and exits with an error
+fn usage(error: &str) -> ! {
+    eprintln!("deck: {}\n\n{}", error, USAGE);
+    process::exit(2);
+}
+
+/// Creates a deck shuffled by the seed, or randomly if there is none
+fn shuffled_deck(options: &Options) -> Deck<Card> {
+    let mut deck = Deck::new();
+    match options.seed {
+        Some(seed) => deck.seeded_shuffle(seed),
+        None => deck.shuffle(),
+    }
+    deck
+}
+
+fn shuffle(options: &Options) -> Result<String, String> {
+    let mut deck = shuffled_deck(options);
+    let cards = deck.deal(deck.undealt_count());
+    Ok(if options.json {
+        json_cards(&cards).to_string()
+    } else {
+        text_cards(&cards, options)
+    })
+}
+
+fn deal(options: &Options, args: &[String]) -> Result<String, String> {
+    let number = |i: usize| -> Result<usize, String> {
+        args.get(i)
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| "deal needs the number of hands and cards".to_string())
+    };
+    let (hands, cards) = (number(0)?, number(1)?);
+    match hands.checked_mul(cards) {
+        Some(total) if total <= 52 => {}
+        Some(total) => return Err(format!("Cannot deal {} cards from 52", total)),
+        None => return Err(format!("Cannot deal {} hands of {} cards", hands, cards)),
+    }
+
+    let mut deck = shuffled_deck(options);
+    let mut dealt = vec![Hand::new(); hands];
+    // Deal one card at a time to each hand in turn, like a dealer would
+    for _ in 0..cards {
+        for hand in dealt.iter_mut() {
+            deck.deal_to_hand(hand, 1);
+        }
+    }
+    Ok(if options.json {
+        let hands = dealt
+            .iter()
+            .map(|h| json_cards(h.cards()))
+            .collect::<Vec<_>>();
+        json!({ "hands": hands, "undealt": deck.undealt_count() }).to_string()
+    } else {
+        let hands = dealt
+            .iter()
+            .enumerate()
+            .map(|(i, h)| format!("{}: {}", i + 1, text_cards(h.cards(), options)))
+            .collect::<Vec<_>>();
+        hands.join("\n")
+    })
+}
+
+fn parse(options: &Options, args: &[String]) -> Result<String, String> {
+    let cards = parse_cards(args, options)?;
+    let mut output = String::new();
+    if options.json {
+        let cards = cards
+            .iter()
+            .map(|card| {
+                json!({
+                    "short": short(card, options),
+                    "name": name(card, options),
+                    "glyph": glyph(card),
+                    "ordinal": card.ordinal(),
+                })
+            })
+            .collect::<Vec<_>>();
+        output = Value::from(cards).to_string();
+    } else {
+        for card in &cards {
+            let _ = writeln!(
+                output,
+                "{}  {}  {}  {}",
+                pretty(card, options),
+                glyph(card),
+                card.ordinal(),
+                name(card, options)
+            );
+        }
+        output.pop();
+    }
+    Ok(output)
+}
+
+fn convert(options: &Options, args: &[String]) -> Result<String, String> {
+    let (format, args) = args
+        .split_first()
+        .ok_or_else(|| "convert needs a format".to_string())?;
+    let to: fn(&Card, &Options) -> String = match format.as_str() {
+        "short" => short,
+        "pretty" => pretty,
+        "name" => name,
+        "glyph" => |card, _| glyph(card),
+        "ordinal" => |card, _| card.ordinal().to_string(),
+        _ => return Err(format!("Unknown format \"{}\"", format)),
+    };
+    let cards = parse_cards(args, options)?;
+    let converted = cards.iter().map(|c| to(c, options)).collect::<Vec<_>>();
+    Ok(if options.json {
+        Value::from(converted).to_string()
+    } else if format == "name" {
+        converted.join("\n")
+    } else {
+        converted.join(" ")
+    })
+}
+
+fn eval(options: &Options, args: &[String]) -> Result<String, String> {
+    let cards = parse_cards(args, options)?;
+    let value = poker::evaluate(&cards).map_err(|e| e.to_string())?;
+    Ok(if options.json {
+        let ranks = value
+            .ranks
+            .iter()
+            .map(|r| r.to_char().to_string())
+            .collect::<Vec<_>>();
+        json!({
+            "category": value.category.name(),
+            "ranks": ranks,
+            "cards": json_cards(&value.cards),
+        })
+        .to_string()
+    } else {
+        format!("{}: {}", value.category, text_cards(&value.cards, options))
+    })
+}
+
+/// Parses every card in the arguments, which may be a single card in any notation or several
+/// separated by spaces or commas
+fn parse_cards(args: &[String], options: &Options) -> Result<Vec<Card>, String> {
+    if args.is_empty() {
+        return Err("No cards given".to_string());
+    }
+    let mut cards = Vec::new();
+    for arg in args {
+        if let Ok(card) = notation::parse_card(arg, Strictness::Lenient) {
+            cards.push(card);
+        } else if let Ok(card) = options.locale.parse_card(arg) {
+            cards.push(card);
+        } else {
+            let hand = Hand::parse(arg).map_err(|errors| {
+                let errors = errors
+                    .iter()
+                    .map(|e| format!("\"{}\": {}", e.token, e.reason))
+                    .collect::<Vec<_>>();
+                errors.join(", ")
+            })?;
+            cards.extend_from_slice(hand.cards());
+        }
+    }
+    Ok(cards)
+}
+
+fn short(card: &Card, options: &Options) -> String {
+    match options.locale {
+        Locale::English => card.to_str(),
+        locale => locale.card_abbreviation(card),
+    }
+}
+
+fn pretty(card: &Card, options: &Options) -> String {
+    match options.locale {
+        #[cfg(feature = "pretty")]
+        Locale::English => card.to_pretty(),
+        locale => locale.card_abbreviation(card),
+    }
+}
+
+fn name(card: &Card, options: &Options) -> String {
+    options.locale.card_name(card)
+}
+
+fn glyph(card: &Card) -> String {
+    card.to_unicode_glyph()
+        .map(String::from)
+        .unwrap_or_default()
+}
+
+fn text_cards(cards: &[Card], options: &Options) -> String {
+    let cards = cards.iter().map(|c| pretty(c, options)).collect::<Vec<_>>();
+    cards.join(" ")
+}
+
+fn json_cards(cards: &[Card]) -> Value {
+    cards.iter().map(|c| c.to_str()).collect()
+}
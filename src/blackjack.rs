@@ -0,0 +1,270 @@
+//! Blackjack against the dealer.
+//!
+//! The player and the dealer are each dealt two cards, and the dealer's second card stays face
+//! down while the player plays. The player draws cards until they stand or go over 21, then the
+//! dealer draws until they have 17 or more. Aces count 11 unless that would go over 21, court
+//! cards count 10 and other cards count their pip value. The discards are shuffled back into the
+//! shoe when it runs low.
+
+#[cfg(feature = "std")]
+use rand::Rng;
+
+use super::game::{Game, Table, Transition, Turn, Zone};
+#[cfg(feature = "std")]
+use super::mcts::{self, Playable};
+use super::*;
+
+/// The seat of the player
+pub const PLAYER: usize = 0;
+
+/// The seat of the dealer
+pub const DEALER: usize = 1;
+
+/// The fewest cards left in the shoe before the discards are shuffled back in
+pub const RESHUFFLE_AT: usize = 15;
+
+/// The phase of a round
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// The player is drawing cards and the dealer's hole card is face down
+    Playing,
+    /// The round has been settled and the next one can be dealt
+    RoundOver,
+}
+
+/// The actions the player can take
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Draws another card
+    Hit,
+    /// Stops drawing and lets the dealer play
+    Stand,
+    /// Discards both hands and deals a new round
+    Deal,
+}
+
+/// How a round ended for the player
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The player had 21 with their first two cards and the dealer did not
+    Blackjack,
+    /// The player beat the dealer
+    Win,
+    /// The player and the dealer tied
+    Push,
+    /// The dealer beat the player
+    Lose,
+}
+
+/// The rules of blackjack, which also keep the score
+#[derive(Clone, Debug, Default)]
+pub struct Blackjack {
+    /// How the last round ended, if it has been settled
+    pub outcome: Option<Outcome>,
+    /// The number of rounds won, including blackjacks
+    pub wins: usize,
+    /// The number of rounds lost
+    pub losses: usize,
+    /// The number of rounds tied
+    pub pushes: usize,
+    /// The seed of the next shuffle of the shoe
+    pub seed: u64,
+}
+
+/// Returns the best total of the cards and whether it counts an Ace as 11
+///
+/// # Example
+///
+/// ```
+/// use deckofcards::{blackjack, hand, Cards};
+///
+/// assert_eq!(blackjack::total(hand!("AS", "6D").cards()), (17, true));
+/// assert_eq!(blackjack::total(hand!("AS", "6D", "KC").cards()), (17, false));
+/// ```
+pub fn total(cards: &[Card]) -> (usize, bool) {
+    let mut total = 0;
+    let mut aces = 0;
+    for card in cards {
+        total += match card.rank {
+            Rank::Ace => {
+                aces += 1;
+                1
+            }
+            Rank::Jack | Rank::Queen | Rank::King => 10,
+            rank => rank.ordinal() + 2,
+        };
+    }
+    if aces > 0 && total + 10 <= 21 {
+        (total + 10, true)
+    } else {
+        (total, false)
+    }
+}
+
+fn is_blackjack(cards: &[Card]) -> bool {
+    cards.len() == 2 && total(cards).0 == 21
+}
+
+impl Blackjack {
+    /// Starts a game with a shoe shuffled by the seed and deals the first round
+    pub fn deal(seed: u64) -> Game<Blackjack> {
+        let mut deck = Deck::new();
+        deck.seeded_shuffle(seed);
+        let table = Table::new(deck, &["Player", "Dealer"]);
+        let rules = Blackjack {
+            seed: seed.wrapping_add(1),
+            ..Blackjack::default()
+        };
+        let mut game = Game::new(table, rules).unwrap();
+        game.act(PLAYER, &Action::Deal).unwrap();
+        game
+    }
+
+    /// Plays out the dealer's hand and settles the round
+    fn settle(&mut self, table: &mut Table) -> Result<Transition<Phase>, &'static str> {
+        let player = table.seats[PLAYER].hand.cards().to_vec();
+        let (player_total, _) = total(&player);
+        if player_total <= 21 && !is_blackjack(&player) {
+            while total(table.seats[DEALER].hand.cards()).0 < 17 {
+                let _ = table.draw(Zone::Hand(DEALER))?;
+            }
+        }
+        let dealer = table.seats[DEALER].hand.cards();
+        let (dealer_total, _) = total(dealer);
+        let outcome = if player_total > 21 {
+            Outcome::Lose
+        } else if is_blackjack(&player) && !is_blackjack(dealer) {
+            Outcome::Blackjack
+        } else if is_blackjack(dealer) && !is_blackjack(&player) {
+            Outcome::Lose
+        } else if dealer_total > 21 || player_total > dealer_total {
+            Outcome::Win
+        } else if player_total == dealer_total {
+            Outcome::Push
+        } else {
+            Outcome::Lose
+        };
+        match outcome {
+            Outcome::Blackjack | Outcome::Win => self.wins += 1,
+            Outcome::Push => self.pushes += 1,
+            Outcome::Lose => self.losses += 1,
+        }
+        self.outcome = Some(outcome);
+        Ok(Transition::Phase(Phase::RoundOver))
+    }
+}
+
+impl game::Rules for Blackjack {
+    type Card = Card;
+    type Hand = Hand;
+    type Phase = Phase;
+    type Action = Action;
+
+    fn initial_phase(&self) -> Phase {
+        Phase::RoundOver
+    }
+
+    fn validate(
+        &self,
+        _table: &Table,
+        turn: &Turn<Phase>,
+        action: &Action,
+    ) -> Result<(), &'static str> {
+        match (turn.phase, action) {
+            (Phase::Playing, Action::Hit | Action::Stand) => Ok(()),
+            (Phase::RoundOver, Action::Deal) => Ok(()),
+            (Phase::Playing, Action::Deal) => Err("The round is not over"),
+            (Phase::RoundOver, _) => Err("The round is over"),
+        }
+    }
+
+    fn apply(
+        &mut self,
+        table: &mut Table,
+        _turn: &Turn<Phase>,
+        action: &Action,
+    ) -> Result<Transition<Phase>, &'static str> {
+        match action {
+            Action::Hit => {
+                let _ = table.draw(Zone::Hand(PLAYER))?;
+                if total(table.seats[PLAYER].hand.cards()).0 > 21 {
+                    return self.settle(table);
+                }
+                Ok(Transition::Stay)
+            }
+            Action::Stand => self.settle(table),
+            Action::Deal => {
+                for seat in [PLAYER, DEALER] {
+                    let cards = table.seats[seat].hand.cards().to_vec();
+                    for card in &cards {
+                        table.move_card(Zone::Hand(seat), Zone::Discard, card)?;
+                    }
+                }
+                if table.deck.undealt_count() < RESHUFFLE_AT {
+                    let discards = table.area_mut(Zone::Discard).unwrap();
+                    let mut cards = core::mem::take(&mut discards.cards);
+                    cards.extend_from_slice(table.deck.cards());
+                    table.deck = Deck::from_cards(&cards);
+                    table.deck.seeded_shuffle(self.seed);
+                    self.seed = self.seed.wrapping_add(1);
+                }
+                let _ = table.deal(2);
+                self.outcome = None;
+                let player = table.seats[PLAYER].hand.cards();
+                let dealer = table.seats[DEALER].hand.cards();
+                if is_blackjack(player) || is_blackjack(dealer) {
+                    return self.settle(table);
+                }
+                Ok(Transition::Phase(Phase::Playing))
+            }
+        }
+    }
+
+    fn is_public(&self, turn: &Turn<Phase>, zone: Zone, index: usize) -> bool {
+        // Only the dealer's hole card is hidden, until the round is settled
+        zone != Zone::Hand(DEALER) || index != 1 || turn.phase == Phase::RoundOver
+    }
+}
+
+/// Searches a round of blackjack, which is over once it is settled
+#[cfg(feature = "std")]
+impl Playable for Game<Blackjack> {
+    type Action = Action;
+
+    fn players(&self) -> usize {
+        2
+    }
+
+    fn to_move(&self) -> Option<usize> {
+        match self.turn().phase {
+            Phase::Playing => Some(PLAYER),
+            Phase::RoundOver => None,
+        }
+    }
+
+    fn legal_actions(&self) -> Vec<Action> {
+        match self.to_move() {
+            Some(_) => vec![Action::Hit, Action::Stand],
+            None => Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, action: &Action) {
+        self.act(PLAYER, action)
+            .expect("A legal action was refused");
+    }
+
+    /// Rewards the player 1 for a win, 0.5 for a push and 0 for a loss, and the dealer the rest
+    fn rewards(&self) -> Vec<f64> {
+        let player = match self.rules.outcome {
+            Some(Outcome::Blackjack | Outcome::Win) => 1.0,
+            Some(Outcome::Push) | None => 0.5,
+            Some(Outcome::Lose) => 0.0,
+        };
+        vec![player, 1.0 - player]
+    }
+
+    fn determinize<R: Rng>(&self, seat: usize, rng: &mut R) -> Option<Self> {
+        mcts::determinized(self, seat, &[], rng)
+    }
+}
@@ -0,0 +1,304 @@
+//! A C interface to decks and hands.
+//!
+//! Decks and hands are opaque handles which are created by a `_new` function and must be released
+//! with the matching `_free` function. A card is passed as its `Card::ordinal()`, a number from 0
+//! to 51. Functions which are given a null handle do nothing and return false or 0.
+//!
+//! The header `include/deckofcards.h` is generated from this module with
+//! `cbindgen --config cbindgen.toml --output include/deckofcards.h src/capi.rs`, and a test
+//! checks that it is up to date.
+//!
+//! # Safety
+//!
+//! Every handle must be one returned by this interface that has not been freed, and every buffer
+//! must be valid for the length that is passed with it. Handles must not be shared between threads
+//! without a lock.
+
+// Each function has the same safety requirements, which are described above
+#![allow(clippy::missing_safety_doc)]
+
+use core::ffi::{c_char, CStr};
+use core::slice;
+
+use super::*;
+
+/// An opaque deck of cards
+pub struct Deck(super::Deck<Card>);
+
+/// An opaque hand of cards
+pub struct Hand(super::Hand);
+
+/// The orders cards can be sorted in, see `SortCards`
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    SuitAscendingRank,
+    SuitDescendingRank,
+    DescendingRankSuit,
+    AscendingRankSuit,
+}
+
+/// Writes the ordinals of the cards to `out`, as many as fit, and returns the number of cards
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes of `capacity` bytes.
+unsafe fn to_ordinals(cards: &[Card], out: *mut u8, capacity: usize) -> usize {
+    if !out.is_null() {
+        let out = slice::from_raw_parts_mut(out, capacity);
+        for (o, card) in out.iter_mut().zip(cards) {
+            *o = card.ordinal() as u8;
+        }
+    }
+    cards.len()
+}
+
+/// Reads `len` card ordinals, returning `None` if one is not a card
+///
+/// # Safety
+///
+/// `cards` must be null, when `len` must be 0, or valid for reads of `len` bytes.
+unsafe fn from_ordinals(cards: *const u8, len: usize) -> Option<Vec<Card>> {
+    if cards.is_null() {
+        return (len == 0).then(Vec::new);
+    }
+    let cards = slice::from_raw_parts(cards, len);
+    cards
+        .iter()
+        .map(|c| Card::from_ordinal(*c as usize).ok())
+        .collect()
+}
+
+/// Creates a standard deck of 52 cards
+#[no_mangle]
+pub extern "C" fn deckofcards_deck_new() -> *mut Deck {
+    Box::into_raw(Box::new(Deck(super::Deck::new())))
+}
+
+/// Creates a deck from the cards, from the bottom to the top. Returns null if a card is not valid.
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_deck_from_cards(cards: *const u8, len: usize) -> *mut Deck {
+    match from_ordinals(cards, len) {
+        Some(cards) => Box::into_raw(Box::new(Deck(super::Deck::from_cards(&cards)))),
+        None => core::ptr::null_mut(),
+    }
+}
+
+/// Frees a deck
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_deck_free(deck: *mut Deck) {
+    if !deck.is_null() {
+        drop(Box::from_raw(deck));
+    }
+}
+
+/// Shuffles the undealt cards into a random order
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_deck_shuffle(deck: *mut Deck) {
+    if let Some(deck) = deck.as_mut() {
+        deck.0.shuffle();
+    }
+}
+
+/// Shuffles the undealt cards into the same order as `Cards::seeded_shuffle`
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_deck_seeded_shuffle(deck: *mut Deck, seed: u64) {
+    if let Some(deck) = deck.as_mut() {
+        deck.0.seeded_shuffle(seed);
+    }
+}
+
+/// Deals the top card into `card`. Returns false if there are no cards left.
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_deck_deal_one(deck: *mut Deck, card: *mut u8) -> bool {
+    match (deck.as_mut(), card.as_mut()) {
+        (Some(deck), Some(card)) => match deck.0.deal_one() {
+            Ok(dealt) => {
+                *card = dealt.ordinal() as u8;
+                true
+            }
+            Err(_) => false,
+        },
+        _ => false,
+    }
+}
+
+/// Deals up to `count` cards from the top of the deck into the hand. Returns the number dealt.
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_deck_deal_to_hand(
+    deck: *mut Deck,
+    hand: *mut Hand,
+    count: usize,
+) -> usize {
+    match (deck.as_mut(), hand.as_mut()) {
+        (Some(deck), Some(hand)) => deck.0.deal_to_hand(&mut hand.0, count),
+        _ => 0,
+    }
+}
+
+/// Puts the dealt cards back on top of the deck, so the cards dealt next are the ones dealt before
+/// in the same order
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_deck_reset(deck: *mut Deck) {
+    if let Some(deck) = deck.as_mut() {
+        deck.0.reset();
+    }
+}
+
+/// Returns the number of undealt cards
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_deck_undealt_count(deck: *const Deck) -> usize {
+    deck.as_ref().map_or(0, |deck| deck.0.undealt_count())
+}
+
+/// Returns the number of dealt cards
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_deck_dealt_count(deck: *const Deck) -> usize {
+    deck.as_ref().map_or(0, |deck| deck.0.dealt_count())
+}
+
+/// Copies up to `capacity` undealt cards, from the bottom to the top, into `out`. Returns the
+/// number of undealt cards, which may be more than were copied.
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_deck_cards(
+    deck: *const Deck,
+    out: *mut u8,
+    capacity: usize,
+) -> usize {
+    deck.as_ref()
+        .map_or(0, |deck| to_ordinals(&deck.0.cards, out, capacity))
+}
+
+/// Creates an empty hand
+#[no_mangle]
+pub extern "C" fn deckofcards_hand_new() -> *mut Hand {
+    Box::into_raw(Box::new(Hand(super::Hand::new())))
+}
+
+/// Frees a hand
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_hand_free(hand: *mut Hand) {
+    if !hand.is_null() {
+        drop(Box::from_raw(hand));
+    }
+}
+
+/// Adds a card to the hand. Returns false if the card is not valid.
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_hand_push(hand: *mut Hand, card: u8) -> bool {
+    match (hand.as_mut(), Card::from_ordinal(card as usize)) {
+        (Some(hand), Ok(card)) => {
+            hand.0.push_card(card);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Removes the first matching card from the hand. Returns false if there is none.
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_hand_remove(hand: *mut Hand, card: u8) -> bool {
+    match (hand.as_mut(), Card::from_ordinal(card as usize)) {
+        (Some(hand), Ok(card)) => hand.0.remove_card(&card),
+        _ => false,
+    }
+}
+
+/// Removes every card from the hand
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_hand_clear(hand: *mut Hand) {
+    if let Some(hand) = hand.as_mut() {
+        hand.0.clear();
+    }
+}
+
+/// Returns the number of cards in the hand
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_hand_len(hand: *const Hand) -> usize {
+    hand.as_ref().map_or(0, |hand| hand.0.len())
+}
+
+/// Copies up to `capacity` cards of the hand into `out`. Returns the number of cards in the
+/// hand, which may be more than were copied.
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_hand_cards(
+    hand: *const Hand,
+    out: *mut u8,
+    capacity: usize,
+) -> usize {
+    hand.as_ref()
+        .map_or(0, |hand| to_ordinals(&hand.0.cards, out, capacity))
+}
+
+/// Shuffles the hand into a random order
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_hand_shuffle(hand: *mut Hand) {
+    if let Some(hand) = hand.as_mut() {
+        hand.0.shuffle();
+    }
+}
+
+/// Shuffles the hand into the same order as `Cards::seeded_shuffle`
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_hand_seeded_shuffle(hand: *mut Hand, seed: u64) {
+    if let Some(hand) = hand.as_mut() {
+        hand.0.seeded_shuffle(seed);
+    }
+}
+
+/// Sorts the hand in the order, which is one of the `SortOrder` values. Returns false if the order
+/// is not one of them.
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_hand_sort(hand: *mut Hand, order: u32) -> bool {
+    // The order is passed as a number because C may pass any value for an enum
+    let order = match order {
+        0 => SortOrder::SuitAscendingRank,
+        1 => SortOrder::SuitDescendingRank,
+        2 => SortOrder::DescendingRankSuit,
+        3 => SortOrder::AscendingRankSuit,
+        _ => return false,
+    };
+    match hand.as_mut() {
+        Some(hand) => {
+            match order {
+                SortOrder::SuitAscendingRank => hand.0.sort_suit_ascending_rank(),
+                SortOrder::SuitDescendingRank => hand.0.sort_suit_descending_rank(),
+                SortOrder::DescendingRankSuit => hand.0.sort_descending_rank_suit(),
+                SortOrder::AscendingRankSuit => hand.0.sort_ascending_rank_suit(),
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Writes the short string of the card, e.g. "AS", and a terminating NUL into `out`, which must
+/// have room for 3 bytes. Returns false if the card is not valid.
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_card_to_str(card: u8, out: *mut c_char) -> bool {
+    match Card::from_ordinal(card as usize) {
+        Ok(card) if !out.is_null() => {
+            let s = card.to_str();
+            let out = slice::from_raw_parts_mut(out, 3);
+            for (o, b) in out.iter_mut().zip(s.bytes().chain(Some(0))) {
+                *o = b as c_char;
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Parses a NUL terminated card string such as "AS" or "10h". Returns the ordinal of the card or
+/// -1 if it is not valid.
+#[no_mangle]
+pub unsafe extern "C" fn deckofcards_card_from_str(s: *const c_char) -> i32 {
+    if s.is_null() {
+        return -1;
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .ok()
+        .and_then(|s| notation::parse_card(s, notation::Strictness::Lenient).ok())
+        .map_or(-1, |card| card.ordinal() as i32)
+}
@@ -1,8 +1,8 @@
 extern crate rand;
 
-use std::cmp::Ordering;
-use std::fmt::{self};
-use std::slice::Iter;
+use core::cmp::Ordering;
+use core::fmt::{self};
+use core::slice::Iter;
 
 use super::*;
 
@@ -15,7 +15,7 @@ use super::*;
 /// use deckofcards::{Card, Rank, Suit};
 /// let card = Card::new(Rank::Jack, Suit::Hearts);
 /// ```
-#[derive(Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Debug)]
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
 pub struct Card {
     /// The card's `Rank`, e.g. Jack
     pub rank: Rank,
@@ -40,6 +40,12 @@ impl Ord for Card {
     }
 }
 
+impl PartialOrd for Card {
+    fn partial_cmp(&self, other: &Card) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 pub trait DisplayCard {
     fn to_str(&self) -> String;
     fn from_str(s: &str) -> Result<Self, &'static str>
@@ -48,6 +54,19 @@ pub trait DisplayCard {
     #[cfg(feature = "pretty")]
     fn to_pretty(&self) -> String;
     fn name(&self) -> String;
+    /// Returns the character for the card from the Unicode Playing Cards block, or `None` if it
+    /// has none. By default no card has one.
+    fn to_unicode_glyph(&self) -> Option<char> {
+        None
+    }
+    /// Creates a card from a character in the Unicode Playing Cards block. By default no
+    /// character is a card.
+    fn from_unicode_glyph(_ch: char) -> Result<Self, &'static str>
+    where
+        Self: Sized,
+    {
+        Err("Not a playing card glyph")
+    }
 }
 
 impl DisplayCard for Card {
@@ -83,34 +102,63 @@ impl DisplayCard for Card {
         Err("Invalid string")
     }
 
+    /// Returns the rank and the suit symbol colored by the current `Theme`, e.g. "A♠"
     #[cfg(feature = "pretty")]
     fn to_pretty(&self) -> String {
-        use colored::Colorize;
-
-        format!(
-            "{}{}",
-            self.rank.to_char(),
-            match self.suit.to_color() {
-                suit::Color::Black => self
-                    .suit
-                    .to_unicode()
-                    .to_string()
-                    .as_str()
-                    .custom_color(GRAY),
-                suit::Color::Red => self.suit.to_unicode().to_string().as_str().red(),
-            }
-        )
+        theme::Theme::current().pretty_card(self)
     }
 
     /// Returns an English formatted name of the card, e.g. "Ace of Spades"
     fn name(&self) -> String {
         format!("{} of {}", self.rank.to_str(), self.suit.to_str())
     }
+
+    /// Returns the character for the card from the Unicode Playing Cards block, e.g. '🂡' for
+    /// the Ace of Spades
+    fn to_unicode_glyph(&self) -> Option<char> {
+        let suit = match self.suit {
+            Suit::Spades => 0x1F0A0,
+            Suit::Hearts => 0x1F0B0,
+            Suit::Diamonds => 0x1F0C0,
+            Suit::Clubs => 0x1F0D0,
+        };
+        // The Knight at 12 between the Jack and the Queen is skipped
+        let rank = match self.rank {
+            Rank::Ace => 1,
+            Rank::Jack => 11,
+            Rank::Queen => 13,
+            Rank::King => 14,
+            rank => rank.ordinal() as u32 + 2,
+        };
+        char::from_u32(suit + rank)
+    }
+
+    /// Creates a card from a character in the Unicode Playing Cards block, e.g. '🂡' returns
+    /// the Ace of Spades
+    fn from_unicode_glyph(ch: char) -> Result<Card, &'static str> {
+        let code = ch as u32;
+        let suit = match code & !0xF {
+            0x1F0A0 => Suit::Spades,
+            0x1F0B0 => Suit::Hearts,
+            0x1F0C0 => Suit::Diamonds,
+            0x1F0D0 => Suit::Clubs,
+            _ => return Err("Not a playing card glyph"),
+        };
+        let rank = match code & 0xF {
+            1 => Rank::Ace,
+            n @ 2..=10 => Rank::ranks()[n as usize - 2],
+            11 => Rank::Jack,
+            13 => Rank::Queen,
+            14 => Rank::King,
+            _ => return Err("Not a playing card glyph"),
+        };
+        Ok(Card::new(rank, suit))
+    }
 }
 
 impl Card {
     /// Creates a card with the given suit and rank
-    pub fn new(rank: Rank, suit: Suit) -> Card {
+    pub const fn new(rank: Rank, suit: Suit) -> Card {
         Card { rank, suit }
     }
 
@@ -162,6 +210,17 @@ impl Card {
         self.suit.ordinal() * 13 + self.rank.ordinal()
     }
 
+    /// Creates the card with the ordinal returned by `ordinal()`
+    pub fn from_ordinal(ordinal: usize) -> Result<Card, &'static str> {
+        if ordinal >= 52 {
+            return Err("Invalid ordinal");
+        }
+        Ok(Card::new(
+            Rank::ranks()[ordinal % 13],
+            Suit::suits()[ordinal / 13],
+        ))
+    }
+
     /// Tests if the card is Hearts
     pub fn is_hearts(&self) -> bool {
         self.suit == Suit::Hearts
@@ -398,6 +457,6 @@ impl Card {
     }
 
     pub fn iterator() -> Iter<'static, Card> {
-        Card::all_cards().into_iter()
+        Card::all_cards().iter()
     }
 }
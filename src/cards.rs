@@ -3,6 +3,7 @@ use rand_pcg::Pcg32;
 
 use super::*;
 
+#[cfg(feature = "std")]
 #[test]
 fn test_shuffle() {
     // This code is going create a bunch of decks and shuffle them. It will test that the cards at ends of the deck appear to be shuffled.
@@ -94,6 +95,7 @@ pub trait Cards<C> {
     }
 
     /// Shuffle the cards into a random order
+    #[cfg(feature = "std")]
     fn shuffle(&mut self) {
         self.knuth_shuffle(&mut thread_rng());
     }
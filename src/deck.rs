@@ -1,5 +1,5 @@
-use std::result::Result;
-use std::vec::Vec;
+use alloc::vec::Vec;
+use core::result::Result;
 
 use self::cards::Cards;
 
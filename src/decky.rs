@@ -1,3 +1,6 @@
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
 use crate::{Cards, Deck, Handy};
 
 pub trait Decky<C>: Cards<C>
@@ -12,6 +15,7 @@ where
         }
     }
     /// Creates a new `Deck` containing the standard set of 52 cards
+    #[allow(clippy::new_ret_no_self)]
     fn new() -> Deck<C>;
     /// Add zero or more cards to the undealt part of the deck
     fn push(&mut self, cards: &[C]);
@@ -32,19 +36,19 @@ where
     /// Tells you the top card (very next to be drawn) in the undealt deck
     /// without dealing it.
     fn top_card(&self) -> Option<C> {
-        self.cards().last().map(|card| card.clone())
+        self.cards().last().cloned()
     }
     /// Tells you the bottom card (very last to be drawn) in the undealt deck
     /// without dealing it.
     fn bottom_card(&self) -> Option<C> {
-        self.cards().first().map(|card| card.clone())
+        self.cards().first().cloned()
     }
     /// Deals the card from the undealt pile. If there are no cards left, the function
     /// will return an error.
     fn deal_one(&mut self) -> Result<C, &'static str>;
     /// Deals one or more card from the undealt pile and returns them as an array.
     fn deal(&mut self, numcards: usize) -> Vec<C> {
-        let mut result: Vec<C> = Vec::with_capacity(numcards as usize);
+        let mut result: Vec<C> = Vec::with_capacity(numcards);
         for _ in 0..numcards {
             if let Ok(card) = self.deal_one() {
                 result.push(card);
@@ -73,6 +77,7 @@ where
     /// to the default order or the last shuffle.
     fn reset(&mut self);
     /// Resets and shuffles the deck
+    #[cfg(feature = "std")]
     fn reset_shuffle(&mut self) {
         self.reset();
         self.shuffle();
@@ -0,0 +1,333 @@
+//! Sampling the cards a player cannot see.
+//!
+//! A bot cannot search a game whose cards it cannot see, so instead it deals the unseen cards at
+//! random into the places it cannot see and searches the game as if that deal were true. Each of
+//! these determinizations must agree with what the bot knows, e.g. a player who showed out of a
+//! suit cannot be dealt a card of that suit. Searching many of them and combining the results is
+//! how Monte Carlo bots play Bridge, Hearts and Skat.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "games")] {
+//! use deckofcards::determinize::{Constraint, Sampler};
+//! use deckofcards::game::Zone;
+//! use deckofcards::hearts::Hearts;
+//! use deckofcards::{Card, Suit};
+//! use rand::SeedableRng;
+//! use rand_pcg::Pcg32;
+//!
+//! let game = Hearts::deal(3);
+//! let info = game.info_set(Some(0), Card::all_cards());
+//! let mut sampler = Sampler::new(&info);
+//! sampler.constrain(Constraint::Void(Zone::Hand(1), Suit::Hearts));
+//!
+//! let deal = sampler.sample(&mut Pcg32::seed_from_u64(1)).unwrap();
+//! assert_eq!(deal.cards(Zone::Hand(1)).len(), 13);
+//! assert!(deal.cards(Zone::Hand(1)).iter().all(|c| c.suit != Suit::Hearts));
+//! # }
+//! ```
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+
+use rand::Rng;
+
+use super::cards::knuth_shuffle;
+use super::game::{Game, InfoSet, Rules, Zone};
+use super::*;
+
+/// Something known about where the unseen cards are
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    /// The zone holds no cards of the suit, e.g. because its player did not follow suit
+    Void(Zone, Suit),
+    /// The zone holds the card, e.g. because its player passed it there
+    Holds(Zone, Card),
+    /// The zone does not hold the card
+    Lacks(Zone, Card),
+    /// The zone holds this many of the unseen cards, in place of the number in the information set.
+    /// A deal with a count other than the number of hidden cards cannot be applied to the game.
+    Count(Zone, usize),
+}
+
+/// The unseen cards a sampler dealt into each zone
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deal {
+    /// The cards of each zone, in the order of the places in it the seat cannot see
+    pub zones: BTreeMap<Zone, Vec<Card>>,
+}
+
+impl Deal {
+    /// Returns the cards dealt to the zone
+    pub fn cards(&self, zone: Zone) -> &[Card] {
+        self.zones.get(&zone).map_or(&[], |cards| cards.as_slice())
+    }
+
+    /// Puts the cards of the deal in the places the seat cannot see in the game the information
+    /// set was taken from, so the game can be played out as if the deal were true. The cards the
+    /// seat can see stay where they are. Zones are not resized, so it is an error if a `Count`
+    /// constraint dealt a zone more or fewer cards than it hides.
+    pub fn apply<R>(
+        &self,
+        game: &mut Game<R>,
+        info: &InfoSet<Card, R::Phase>,
+    ) -> Result<(), &'static str>
+    where
+        R: Rules<Card = Card>,
+    {
+        for (zone, cards) in &self.zones {
+            if info.hidden(*zone) != cards.len() {
+                return Err("Deal does not fit the game");
+            }
+        }
+        for (zone, cards) in &self.zones {
+            if *zone == Zone::Stock {
+                // Only the undealt cards are replaced, so the deck still knows what it dealt
+                game.table.deck.cards = cards.clone();
+                continue;
+            }
+            let hidden = info
+                .zones
+                .get(zone)
+                .ok_or("No such zone")?
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.is_none());
+            let area = game.table.area_mut(*zone).ok_or("No such zone")?;
+            for ((i, _), card) in hidden.zip(cards) {
+                area.mut_cards()[i] = *card;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Says how likely a deal is
+type Weight = Box<dyn Fn(&Deal) -> f64>;
+
+/// The cards a constraint says are in each place
+type Held = BTreeMap<Option<Zone>, Vec<Card>>;
+
+/// Samples deals of the unseen cards of an information set that agree with the constraints
+pub struct Sampler {
+    places: Vec<(Zone, usize)>,
+    unseen: Vec<Card>,
+    constraints: Vec<Constraint>,
+    weight: Option<Weight>,
+    attempts: usize,
+}
+
+impl Sampler {
+    /// Creates a sampler which deals the unseen cards of the information set into the places the
+    /// seat cannot see, which are the stock and any hidden cards of hands and shared areas
+    pub fn new<P>(info: &InfoSet<Card, P>) -> Sampler {
+        let mut places = info
+            .zones
+            .keys()
+            .map(|zone| (*zone, info.hidden(*zone)))
+            .filter(|(_, count)| *count > 0)
+            .collect::<Vec<_>>();
+        if info.stock > 0 {
+            places.push((Zone::Stock, info.stock));
+        }
+        Sampler {
+            places,
+            unseen: info.unseen.clone(),
+            constraints: Vec::new(),
+            weight: None,
+            attempts: 1000,
+        }
+    }
+
+    /// Adds a constraint every deal must agree with
+    pub fn constrain(&mut self, constraint: Constraint) -> &mut Self {
+        self.constraints.push(constraint);
+        self
+    }
+
+    /// Weighs each deal by how likely it is, from 0 to 1, so a deal of weight 0.5 is sampled half
+    /// as often as one of weight 1. This is for what is suspected rather than known, e.g. that a
+    /// player who bid strongly holds high cards.
+    pub fn weigh<F>(&mut self, weight: F) -> &mut Self
+    where
+        F: Fn(&Deal) -> f64 + 'static,
+    {
+        self.weight = Some(Box::new(weight));
+        self
+    }
+
+    /// Sets how many deals are tried before giving up, 1000 by default
+    pub fn attempts(&mut self, attempts: usize) -> &mut Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Samples a deal. Every deal that agrees with the constraints is equally likely, unless the
+    /// constraints are so tight that random deals rarely agree with them. Then the cards with the
+    /// fewest places they can go are dealt first, which is faster but only close to uniform.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Result<Deal, &'static str> {
+        let places = self.counted_places()?;
+        let (held, free) = self.held_cards(&places)?;
+        for _ in 0..self.attempts {
+            if let Some(deal) = self.random_deal(&places, &held, &free, rng) {
+                if self.accept(&deal, rng) {
+                    return Ok(deal);
+                }
+            }
+        }
+        for _ in 0..self.attempts {
+            if let Some(deal) = self.constrained_deal(&places, &held, &free, rng) {
+                if self.accept(&deal, rng) {
+                    return Ok(deal);
+                }
+            }
+        }
+        Err("No deal agrees with the constraints")
+    }
+
+    /// Tests if the card may be dealt to the zone, where `None` is out of play
+    fn allowed(&self, zone: Option<Zone>, card: &Card) -> bool {
+        self.constraints.iter().all(|c| match (*c, zone) {
+            (Constraint::Holds(z, held), zone) if held == *card => zone == Some(z),
+            (Constraint::Void(z, suit), Some(zone)) => z != zone || card.suit != suit,
+            (Constraint::Lacks(z, lacked), Some(zone)) => z != zone || lacked != *card,
+            _ => true,
+        })
+    }
+
+    /// Returns the number of cards to deal to each place, with `None` for any unseen cards left
+    /// over, which are out of play
+    fn counted_places(&self) -> Result<Vec<(Option<Zone>, usize)>, &'static str> {
+        let mut places = self.places.clone();
+        for constraint in &self.constraints {
+            if let Constraint::Count(zone, count) = *constraint {
+                match places.iter_mut().find(|(z, _)| *z == zone) {
+                    Some(place) => place.1 = count,
+                    None => places.push((zone, count)),
+                }
+            }
+        }
+        let total = places.iter().map(|(_, count)| count).sum::<usize>();
+        if total > self.unseen.len() {
+            return Err("Too few unseen cards for the hidden places");
+        }
+        let mut places = places
+            .into_iter()
+            .map(|(zone, count)| (Some(zone), count))
+            .collect::<Vec<_>>();
+        places.push((None, self.unseen.len() - total));
+        Ok(places)
+    }
+
+    /// Splits the unseen cards into those a constraint says where they are and the rest
+    fn held_cards(
+        &self,
+        places: &[(Option<Zone>, usize)],
+    ) -> Result<(Held, Vec<Card>), &'static str> {
+        let mut held = Held::new();
+        let mut free = self.unseen.clone();
+        for constraint in &self.constraints {
+            if let Constraint::Holds(zone, card) = *constraint {
+                let i = free
+                    .iter()
+                    .position(|c| *c == card)
+                    .ok_or("Held card is not unseen")?;
+                if !self.allowed(Some(zone), &card) {
+                    return Err("Constraints contradict each other");
+                }
+                held.entry(Some(zone)).or_default().push(free.remove(i));
+            }
+        }
+        for (zone, cards) in &held {
+            let count = places.iter().find(|(z, _)| z == zone).map_or(0, |p| p.1);
+            if cards.len() > count {
+                return Err("Zone holds more cards than it has places");
+            }
+        }
+        Ok((held, free))
+    }
+
+    /// Deals the free cards at random and returns the deal if it agrees with the constraints
+    fn random_deal<R: Rng>(
+        &self,
+        places: &[(Option<Zone>, usize)],
+        held: &Held,
+        free: &[Card],
+        rng: &mut R,
+    ) -> Option<Deal> {
+        let mut deck = Deck::from_cards(free);
+        deck.knuth_shuffle(rng);
+        let mut zones = BTreeMap::new();
+        for (zone, count) in places {
+            let mut cards = held.get(zone).cloned().unwrap_or_default();
+            let dealt = deck.deal(count - cards.len());
+            if !dealt.iter().all(|c| self.allowed(*zone, c)) {
+                return None;
+            }
+            cards.extend(dealt);
+            if let Some(zone) = zone {
+                let _ = zones.insert(*zone, cards);
+            }
+        }
+        Some(Deal { zones })
+    }
+
+    /// Deals the free cards with the fewest places they may go first, each to one of those
+    /// places chosen in proportion to how many cards it still needs
+    fn constrained_deal<R: Rng>(
+        &self,
+        places: &[(Option<Zone>, usize)],
+        held: &Held,
+        free: &[Card],
+        rng: &mut R,
+    ) -> Option<Deal> {
+        let mut cards = free.to_vec();
+        knuth_shuffle(&mut cards, rng);
+        let choices = |card: &Card| {
+            places
+                .iter()
+                .filter(|(z, _)| self.allowed(*z, card))
+                .count()
+        };
+        cards.sort_by_key(choices);
+
+        let mut dealt = places
+            .iter()
+            .map(|(zone, _)| held.get(zone).cloned().unwrap_or_default())
+            .collect::<Vec<_>>();
+        for card in cards {
+            let open = places
+                .iter()
+                .enumerate()
+                .filter(|(i, (zone, count))| dealt[*i].len() < *count && self.allowed(*zone, &card))
+                .map(|(i, (_, count))| (i, count - dealt[i].len()))
+                .collect::<Vec<_>>();
+            let total = open.iter().map(|(_, needed)| needed).sum::<usize>();
+            if total == 0 {
+                return None;
+            }
+            let mut pick = rng.gen_range(0..total);
+            for (i, needed) in open {
+                if pick < needed {
+                    dealt[i].push(card);
+                    break;
+                }
+                pick -= needed;
+            }
+        }
+        let zones = places
+            .iter()
+            .zip(dealt)
+            .filter_map(|((zone, _), cards)| zone.map(|z| (z, cards)))
+            .collect();
+        Some(Deal { zones })
+    }
+
+    fn accept<R: Rng>(&self, deal: &Deal, rng: &mut R) -> bool {
+        match &self.weight {
+            Some(weight) => rng.gen::<f64>() < weight(deal),
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,438 @@
+//! A common backbone for card games.
+//!
+//! A `Table` holds the seats with their hands, the shared areas such as the discard pile and the
+//! draw `Deck`. A `Game` couples a table with a set of `Rules` that decide which actions are
+//! allowed and how they change the table, and it tracks whose turn it is and the phase of the
+//! turn.
+
+use alloc::collections::BTreeMap;
+use core::fmt::Debug;
+
+use super::*;
+
+/// Identifies a place on the table where cards can be held
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Zone {
+    /// The hand of the seat with the given index
+    Hand(usize),
+    /// The undealt cards of the draw deck
+    Stock,
+    /// The discard pile
+    Discard,
+    /// Cards played face up in the middle of the table
+    Board,
+    /// Any other shared area a game needs, as returned by `Table::add_area`
+    Area(usize),
+}
+
+/// A `Seat` is a place at the table with the hand of the player sitting there
+#[derive(Clone)]
+pub struct Seat<H> {
+    /// The name of the player
+    pub name: String,
+    /// The cards the player is holding
+    pub hand: H,
+}
+
+/// A `Table` holds the seats, the shared areas and the draw deck of a game.
+#[derive(Clone)]
+pub struct Table<C = Card, H = Hand> {
+    /// The seats in turn order
+    pub seats: Vec<Seat<H>>,
+    /// The deck cards are drawn from, also known as the stock
+    pub deck: Deck<C>,
+    /// The shared areas, by default the discard pile and the board
+    pub areas: BTreeMap<Zone, H>,
+    /// The names of the areas added with `add_area`, indexed by `Zone::Area`
+    pub area_names: Vec<String>,
+}
+
+impl<C, H> Table<C, H>
+where
+    C: Clone + PartialEq,
+    H: Handy<C> + Cards<C>,
+    Deck<C>: Decky<C>,
+{
+    /// Creates a table with the deck, the named seats and an empty discard pile and board
+    pub fn new(deck: Deck<C>, seats: &[&str]) -> Self {
+        let mut areas = BTreeMap::new();
+        let _ = areas.insert(Zone::Discard, H::new());
+        let _ = areas.insert(Zone::Board, H::new());
+        Table {
+            seats: seats
+                .iter()
+                .map(|name| Seat {
+                    name: name.to_string(),
+                    hand: H::new(),
+                })
+                .collect(),
+            deck,
+            areas,
+            area_names: Vec::new(),
+        }
+    }
+
+    /// Adds an empty shared area with the name to the table and returns its zone. If an area
+    /// with the name already exists, its zone is returned instead.
+    pub fn add_area(&mut self, name: &str) -> Zone {
+        if let Some(zone) = self.find_area(name) {
+            return zone;
+        }
+        let zone = Zone::Area(self.area_names.len());
+        self.area_names.push(name.to_string());
+        let _ = self.areas.insert(zone, H::new());
+        zone
+    }
+
+    /// Returns the zone of the area added with the name
+    pub fn find_area(&self, name: &str) -> Option<Zone> {
+        self.area_names
+            .iter()
+            .position(|n| n == name)
+            .map(Zone::Area)
+    }
+
+    /// Returns the cards held in the zone, or `None` if the zone does not exist
+    pub fn cards(&self, zone: Zone) -> Option<&[C]> {
+        match zone {
+            Zone::Stock => Some(self.deck.cards()),
+            zone => self.area(zone).map(|area| area.cards()),
+        }
+    }
+
+    /// Returns the area for the zone, which is a seat's hand or a shared area
+    pub fn area(&self, zone: Zone) -> Option<&H> {
+        match zone {
+            Zone::Hand(seat) => self.seats.get(seat).map(|s| &s.hand),
+            Zone::Stock => None,
+            zone => self.areas.get(&zone),
+        }
+    }
+
+    /// Returns the mutable area for the zone, which is a seat's hand or a shared area
+    pub fn area_mut(&mut self, zone: Zone) -> Option<&mut H> {
+        match zone {
+            Zone::Hand(seat) => self.seats.get_mut(seat).map(|s| &mut s.hand),
+            Zone::Stock => None,
+            zone => self.areas.get_mut(&zone),
+        }
+    }
+
+    /// Deals cards one at a time to each seat in turn until every seat has received
+    /// `numcards` or the deck runs out. Returns the number of cards dealt.
+    pub fn deal(&mut self, numcards: usize) -> usize {
+        let mut dealt = 0;
+        for _ in 0..numcards {
+            for seat in self.seats.iter_mut() {
+                if let Ok(card) = self.deck.deal_one() {
+                    seat.hand.push_card(card);
+                    dealt += 1;
+                } else {
+                    return dealt;
+                }
+            }
+        }
+        dealt
+    }
+
+    /// Deals the top card of the deck into the zone and returns it
+    pub fn draw(&mut self, to: Zone) -> Result<C, &'static str> {
+        if self.area(to).is_none() {
+            return Err("No such zone");
+        }
+        let card = self.deck.deal_one()?;
+        self.area_mut(to).unwrap().push_card(card.clone());
+        Ok(card)
+    }
+
+    /// Moves the card from one zone to another. A card can only be moved out of the stock if it
+    /// is the top card, and a card moved to the stock is placed underneath it.
+    pub fn move_card(&mut self, from: Zone, to: Zone, card: &C) -> Result<(), &'static str> {
+        if to != Zone::Stock && self.area(to).is_none() {
+            return Err("No such zone");
+        }
+        match from {
+            Zone::Stock => {
+                if self.deck.top_card().as_ref() != Some(card) {
+                    return Err("Card is not on top of the stock");
+                }
+                let _ = self.deck.deal_one()?;
+            }
+            from => {
+                let area = self.area_mut(from).ok_or("No such zone")?;
+                if !area.remove_card(card) {
+                    return Err("Card is not in the zone");
+                }
+            }
+        }
+        match to {
+            Zone::Stock => self.deck.cards.insert(0, card.clone()),
+            to => self.area_mut(to).unwrap().push_card(card.clone()),
+        }
+        Ok(())
+    }
+}
+
+/// Whose turn it is and what phase the turn is in
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Turn<P> {
+    /// The index of the seat whose turn it is
+    pub seat: usize,
+    /// The phase of the turn, e.g. drawing or discarding
+    pub phase: P,
+}
+
+/// What happens after an action has been applied
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Transition<P> {
+    /// The same seat continues in the same phase
+    Stay,
+    /// The same seat continues in a new phase
+    Phase(P),
+    /// Play passes to the next seat, starting in the given phase
+    NextSeat(P),
+    /// Play passes to the given seat, starting in the given phase
+    Seat(usize, P),
+    /// The game is over
+    GameOver,
+}
+
+/// The `Rules` of a game validate and apply the actions that players take.
+pub trait Rules {
+    /// The type of card the game is played with
+    type Card: Clone + PartialEq;
+    /// The type of hand and shared area the game uses
+    type Hand: Handy<Self::Card> + Cards<Self::Card>;
+    /// The phases a turn moves through
+    type Phase: Clone + Debug + PartialEq;
+    /// The actions a player can take
+    type Action: Clone + Debug;
+
+    /// The phase the first turn starts in
+    fn initial_phase(&self) -> Self::Phase;
+
+    /// Checks the action is allowed for the seat whose turn it is. The table is not changed if
+    /// this returns an error.
+    fn validate(
+        &self,
+        table: &Table<Self::Card, Self::Hand>,
+        turn: &Turn<Self::Phase>,
+        action: &Self::Action,
+    ) -> Result<(), &'static str>;
+
+    /// Applies a validated action to the table and says what happens next
+    fn apply(
+        &mut self,
+        table: &mut Table<Self::Card, Self::Hand>,
+        turn: &Turn<Self::Phase>,
+        action: &Self::Action,
+    ) -> Result<Transition<Self::Phase>, &'static str>;
+
+    /// Tests if every player can see the card at the index of the zone. A seat always sees its
+    /// own hand. By default the discard pile and the board are face up and everything else is
+    /// face down.
+    fn is_public(&self, _turn: &Turn<Self::Phase>, zone: Zone, _index: usize) -> bool {
+        matches!(zone, Zone::Discard | Zone::Board)
+    }
+}
+
+/// A `Game` is a table being played according to some rules
+pub struct Game<R: Rules> {
+    /// The table the game is played on
+    pub table: Table<R::Card, R::Hand>,
+    /// The rules of the game
+    pub rules: R,
+    turn: Turn<R::Phase>,
+    over: bool,
+}
+
+impl<R> Clone for Game<R>
+where
+    R: Rules + Clone,
+    R::Hand: Clone,
+{
+    fn clone(&self) -> Self {
+        Game {
+            table: self.table.clone(),
+            rules: self.rules.clone(),
+            turn: self.turn.clone(),
+            over: self.over,
+        }
+    }
+}
+
+impl<R> Game<R>
+where
+    R: Rules,
+    Deck<R::Card>: Decky<R::Card>,
+{
+    /// Creates a game where the first seat has the first turn. Fails if the table has no seats.
+    pub fn new(table: Table<R::Card, R::Hand>, rules: R) -> Result<Self, &'static str> {
+        if table.seats.is_empty() {
+            return Err("Table has no seats");
+        }
+        let phase = rules.initial_phase();
+        Ok(Game {
+            table,
+            rules,
+            turn: Turn { seat: 0, phase },
+            over: false,
+        })
+    }
+
+    /// Returns whose turn it is and the phase of the turn
+    pub fn turn(&self) -> &Turn<R::Phase> {
+        &self.turn
+    }
+
+    /// Tests if the game is over
+    pub fn is_over(&self) -> bool {
+        self.over
+    }
+
+    /// Checks if the seat could take the action now without taking it
+    pub fn validate(&self, seat: usize, action: &R::Action) -> Result<(), &'static str> {
+        if self.over {
+            return Err("Game is over");
+        }
+        if seat != self.turn.seat {
+            return Err("Not this seat's turn");
+        }
+        self.rules.validate(&self.table, &self.turn, action)
+    }
+
+    /// Takes the action for the seat, moving the game on to the next phase or turn. Nothing
+    /// changes if the action is not valid now. If the rules fail while applying it, or pass play
+    /// to a seat that does not exist, the table and rules may already have changed while the turn
+    /// has not, so the game should be discarded.
+    pub fn act(&mut self, seat: usize, action: &R::Action) -> Result<(), &'static str> {
+        self.validate(seat, action)?;
+        let transition = self.rules.apply(&mut self.table, &self.turn, action)?;
+        match transition {
+            Transition::Stay => {}
+            Transition::Phase(phase) => self.turn.phase = phase,
+            Transition::NextSeat(phase) => {
+                self.turn = Turn {
+                    seat: (self.turn.seat + 1) % self.table.seats.len(),
+                    phase,
+                }
+            }
+            Transition::Seat(seat, _) if seat >= self.table.seats.len() => {
+                return Err("No such seat");
+            }
+            Transition::Seat(seat, phase) => self.turn = Turn { seat, phase },
+            Transition::GameOver => self.over = true,
+        }
+        Ok(())
+    }
+
+    /// Returns what the seat knows about the game, or what someone who is not playing knows if
+    /// the seat is `None`. The unseen cards are the cards of the universe, such as
+    /// `Card::all_cards()`, less one for each card the seat can see.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "games")] {
+    /// use deckofcards::game::Zone;
+    /// use deckofcards::hearts::Hearts;
+    /// use deckofcards::Card;
+    ///
+    /// let game = Hearts::deal(1);
+    /// let info = game.info_set(Some(0), Card::all_cards());
+    /// assert_eq!(info.hand.len(), 13);
+    /// assert_eq!(info.hidden(Zone::Hand(1)), 13);
+    /// assert_eq!(info.unseen.len(), 39);
+    /// # }
+    /// ```
+    pub fn info_set(
+        &self,
+        seat: Option<usize>,
+        universe: &[R::Card],
+    ) -> InfoSet<R::Card, R::Phase> {
+        let mut unseen = universe.to_vec();
+        let mut zones = BTreeMap::new();
+        let hands = (0..self.table.seats.len()).map(Zone::Hand);
+        for zone in hands.chain(self.table.areas.keys().copied()) {
+            let own = seat.map(Zone::Hand) == Some(zone);
+            let cards = self.table.cards(zone).unwrap_or_default();
+            let shown = cards
+                .iter()
+                .enumerate()
+                .map(|(i, card)| {
+                    if !own && !self.rules.is_public(&self.turn, zone, i) {
+                        return None;
+                    }
+                    if let Some(u) = unseen.iter().position(|u| u == card) {
+                        let _ = unseen.remove(u);
+                    }
+                    Some(card.clone())
+                })
+                .collect();
+            let _ = zones.insert(zone, shown);
+        }
+        InfoSet {
+            seat,
+            hand: seat.map_or(Vec::new(), |s| {
+                self.table.cards(Zone::Hand(s)).unwrap_or_default().to_vec()
+            }),
+            zones,
+            stock: self.table.deck.undealt_count(),
+            turn: self.turn.clone(),
+            over: self.over,
+            unseen,
+        }
+    }
+}
+
+/// What one seat knows about a game, which is what a bot may base its play on and all a client
+/// should be sent
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InfoSet<C = Card, P = ()> {
+    /// The seat the view is for, or `None` for someone who is not playing
+    pub seat: Option<usize>,
+    /// The cards in the seat's own hand
+    pub hand: Vec<C>,
+    /// The cards of every hand and shared area, with `None` in place of each card the seat
+    /// cannot see. The stock is left out since its cards are never seen.
+    pub zones: BTreeMap<Zone, Vec<Option<C>>>,
+    /// The number of cards in the stock
+    pub stock: usize,
+    /// Whose turn it is and the phase of the turn
+    pub turn: Turn<P>,
+    /// Whether the game is over
+    pub over: bool,
+    /// The cards the seat cannot see anywhere, which are in the stock or face down
+    pub unseen: Vec<C>,
+}
+
+impl<C, P> InfoSet<C, P>
+where
+    C: Clone,
+{
+    /// Returns the number of cards in the zone, e.g. in an opponent's hand
+    pub fn count(&self, zone: Zone) -> usize {
+        match zone {
+            Zone::Stock => self.stock,
+            zone => self.zones.get(&zone).map_or(0, |cards| cards.len()),
+        }
+    }
+
+    /// Returns the number of cards in the zone the seat cannot see
+    pub fn hidden(&self, zone: Zone) -> usize {
+        match zone {
+            Zone::Stock => self.stock,
+            zone => self
+                .zones
+                .get(&zone)
+                .map_or(0, |cards| cards.iter().filter(|c| c.is_none()).count()),
+        }
+    }
+
+    /// Returns the cards in the zone the seat can see
+    pub fn visible(&self, zone: Zone) -> Vec<C> {
+        self.zones.get(&zone).map_or(Vec::new(), |cards| {
+            cards.iter().flatten().cloned().collect()
+        })
+    }
+}
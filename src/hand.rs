@@ -1,6 +1,5 @@
-use deref_derive::{Deref, DerefMut};
-use std::fmt::{Display, Formatter, Result};
-use std::ops::AddAssign;
+use core::fmt::{Display, Formatter, Result};
+use core::ops::{AddAssign, Deref, DerefMut};
 
 use self::cards::Cards;
 
@@ -10,14 +9,51 @@ use super::*;
 /// e.g. the cards a person is holding. A hand may be shuffled or sorted
 /// and there are functions for adding or removing cards. Unlike a `Deck`,
 /// there is no concept of dealt or undealt cards.
-#[derive(Clone, Deref, DerefMut)]
+#[derive(Clone)]
 pub struct Hand(Area<Card>);
 
+impl Deref for Hand {
+    type Target = Area<Card>;
+
+    fn deref(&self) -> &Area<Card> {
+        &self.0
+    }
+}
+
+impl DerefMut for Hand {
+    fn deref_mut(&mut self) -> &mut Area<Card> {
+        &mut self.0
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct Area<T> {
     pub cards: Vec<T>,
 }
 
+/// Whether `Hand::parse_with` accepts the same card more than once
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Duplicates {
+    /// The same card may appear more than once, e.g. when playing with several decks
+    #[default]
+    Allow,
+    /// Every card after the first with the same rank and suit is an error
+    Reject,
+}
+
+/// A token that `Hand::parse` could not turn into a card
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvalidCard {
+    /// The index of the token in the string, starting from 0
+    pub index: usize,
+    /// The byte offset into the string where the problem is
+    pub position: usize,
+    /// The token as it was written
+    pub token: String,
+    /// Why the token is not a valid card
+    pub reason: &'static str,
+}
+
 impl Display for Hand {
     fn fmt(&self, f: &mut Formatter) -> Result {
         let mut result = String::with_capacity(self.cards.len() * 3);
@@ -33,13 +69,11 @@ impl Display for Hand {
 
 impl Default for Hand {
     fn default() -> Self {
-        Self {
-            0: hand::Area { cards: Vec::new() },
-        }
+        Self(hand::Area { cards: Vec::new() })
     }
 }
 
-impl<'a> AddAssign<&'a Hand> for Hand {
+impl AddAssign<&Hand> for Hand {
     fn add_assign(&mut self, rhs: &Hand) {
         self.push_hand(rhs);
     }
@@ -71,19 +105,15 @@ impl Handy<Card> for Hand {
 
     /// Makes a `Hand` from a slice
     fn from_cards(cards: &[Card]) -> Self {
-        Self {
-            0: hand::Area {
-                cards: Vec::from(cards),
-            },
-        }
+        Self(hand::Area {
+            cards: Vec::from(cards),
+        })
     }
 
     /// Constructs a `Hand` from a slice of strings with abbreviated card rank / suit values
     fn from_strings(card_slice: &[&str]) -> Self {
         let cards = card_slice.iter().map(|s| card!(s)).collect::<Vec<Card>>();
-        Self {
-            0: hand::Area { cards },
-        }
+        Self(hand::Area { cards })
     }
 
     /// Adds one `Card` to the `Hand`
@@ -137,6 +167,69 @@ impl Handy<Card> for Hand {
 }
 
 impl Hand {
+    /// Parses a `Hand` from cards separated by spaces, commas, semicolons or new lines, e.g.
+    /// "AS KD 7h, 10c". Cards may be written in any of the forms `notation::parse_card` accepts
+    /// that do not contain a space. Returns every invalid card rather than stopping at the first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deckofcards::{Hand, Handy};
+    ///
+    /// let hand = Hand::parse("AS KD 7h, 10c").unwrap();
+    /// assert_eq!(hand.len(), 4);
+    ///
+    /// let errors = Hand::parse("AS ZZ 7h XX").err().unwrap();
+    /// assert_eq!(errors[0].index, 1);
+    /// assert_eq!(errors[1].index, 3);
+    /// ```
+    pub fn parse(s: &str) -> core::result::Result<Hand, Vec<InvalidCard>> {
+        Hand::parse_with(s, Duplicates::Allow)
+    }
+
+    /// Parses a `Hand` like `parse()`, and optionally treats a repeated card as an error
+    pub fn parse_with(
+        s: &str,
+        duplicates: Duplicates,
+    ) -> core::result::Result<Hand, Vec<InvalidCard>> {
+        let mut hand = Hand::new();
+        let mut errors = Vec::new();
+        let separators = |c: char| c.is_whitespace() || c == ',' || c == ';';
+        // Each token is followed by one separator, whose length is added to the offset
+        let mut offset = 0;
+        let tokens = s
+            .split(separators)
+            .map(|t| {
+                let position = offset;
+                offset += t.len();
+                offset += s[offset..].chars().next().map_or(0, char::len_utf8);
+                (position, t)
+            })
+            .filter(|(_, t)| !t.is_empty());
+        for (index, (position, token)) in tokens.enumerate() {
+            let mut invalid = |at: usize, reason| {
+                errors.push(InvalidCard {
+                    index,
+                    position: position + at,
+                    token: token.to_string(),
+                    reason,
+                })
+            };
+            match notation::parse_card(token, notation::Strictness::Lenient) {
+                Ok(card) if duplicates == Duplicates::Reject && hand.cards.contains(&card) => {
+                    invalid(0, "Duplicate card")
+                }
+                Ok(card) => hand.push_card(card),
+                Err(e) => invalid(e.position, e.reason),
+            }
+        }
+        if errors.is_empty() {
+            Ok(hand)
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Returns cards of the specified `Rank`
     pub fn cards_of_rank(&self, rank: Rank) -> Vec<Card> {
         cards_of_rank(&self.cards, rank)
@@ -0,0 +1,385 @@
+//! A record of everything that happened in a hand of a game.
+//!
+//! A `HandHistory` captures the order of the deck before dealing, the seats at the table, and
+//! each deal, move, bet and win in the order they happened. It can be written out as text and
+//! read back, and replayed through a `Table` to check that it is consistent.
+//!
+//! The text format has one entry per line. Blank lines and lines starting with `#` are ignored.
+//!
+//! ```text
+//! seed 42
+//! seat North
+//! seat South
+//! area Crib
+//! deal 0 AS
+//! deal 1 7H
+//! move hand:0 area:0 AS
+//! bet 1 20
+//! win 1 20
+//! ```
+//!
+//! Instead of `seed`, a `deck` line may list the undealt cards from the bottom to the top of the
+//! deck.
+//!
+//! Seat and area names are written with backslashes, newlines, carriage returns and tabs escaped
+//! as `\\`, `\n`, `\r` and `\t`, and a space at either end of the name as `\s`.
+
+use core::fmt;
+use core::str::FromStr;
+
+use super::game::{Table, Zone};
+use super::*;
+
+/// How the deck was ordered before the hand was dealt
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Origin {
+    /// A standard deck shuffled with `Cards::seeded_shuffle` using the seed
+    Seed(u64),
+    /// The undealt cards from the bottom to the top of the deck
+    Cards(Vec<Card>),
+}
+
+impl Origin {
+    /// Creates the deck the hand was dealt from
+    pub fn deck(&self) -> Deck<Card> {
+        match self {
+            Origin::Seed(seed) => {
+                let mut deck = Deck::new();
+                deck.seeded_shuffle(*seed);
+                deck
+            }
+            Origin::Cards(cards) => Deck::from_cards(cards),
+        }
+    }
+}
+
+/// Something that happened during a hand
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Entry {
+    /// The seat was dealt the top card of the deck
+    Deal { seat: usize, card: Card },
+    /// The card was moved between zones
+    Move { from: Zone, to: Zone, card: Card },
+    /// The seat put the amount into the pot
+    Bet { seat: usize, amount: u64 },
+    /// The seat won the amount from the pot
+    Win { seat: usize, amount: u64 },
+}
+
+/// A `HandHistory` is a complete record of a hand which can be replayed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HandHistory {
+    /// How the deck was ordered before dealing
+    pub origin: Origin,
+    /// The names of the seats in turn order
+    pub seats: Vec<String>,
+    /// The names of the shared areas, indexed by `Zone::Area`
+    pub areas: Vec<String>,
+    /// What happened, in order
+    pub entries: Vec<Entry>,
+}
+
+impl HandHistory {
+    /// Creates an empty history for the seats with the deck ordered by the origin
+    pub fn new(origin: Origin, seats: &[&str]) -> HandHistory {
+        HandHistory {
+            origin,
+            seats: seats.iter().map(|s| s.to_string()).collect(),
+            areas: Vec::new(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Creates the table as it was before anything happened
+    pub fn table(&self) -> Table {
+        let seats = self.seats.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        let mut table = Table::new(self.origin.deck(), &seats);
+        self.areas.iter().for_each(|name| {
+            let _ = table.add_area(name);
+        });
+        table
+    }
+
+    /// Returns the total amount bet, or `u64::MAX` if that is too large to count
+    pub fn pot(&self) -> u64 {
+        self.entries.iter().fold(0u64, |pot, e| match e {
+            Entry::Bet { amount, .. } => pot.saturating_add(*amount),
+            _ => pot,
+        })
+    }
+
+    /// Replays every entry on a new table and returns the table as it was at the end of the hand.
+    /// Fails if a dealt card is not the next card in the deck, a move is not possible, a seat
+    /// does not exist, more is won than was bet, or the amounts are too large to add up.
+    pub fn replay(&self) -> Result<Table, &'static str> {
+        let mut table = self.table();
+        let mut bet = 0u64;
+        let mut won = 0u64;
+        for entry in &self.entries {
+            match entry {
+                Entry::Deal { seat, card } => {
+                    if *seat >= table.seats.len() {
+                        return Err("No such seat");
+                    }
+                    if table.deck.top_card() != Some(*card) {
+                        return Err("Dealt card is not the next card in the deck");
+                    }
+                    let _ = table.draw(Zone::Hand(*seat))?;
+                }
+                Entry::Move { from, to, card } => table.move_card(*from, *to, card)?,
+                Entry::Bet { seat, .. } | Entry::Win { seat, .. } if *seat >= table.seats.len() => {
+                    return Err("No such seat");
+                }
+                Entry::Bet { amount, .. } => {
+                    bet = bet.checked_add(*amount).ok_or("Amounts are too large")?;
+                }
+                Entry::Win { amount, .. } => {
+                    won = won.checked_add(*amount).ok_or("Amounts are too large")?;
+                }
+            }
+        }
+        if won > bet {
+            return Err("More was won than was bet");
+        }
+        Ok(table)
+    }
+}
+
+impl fmt::Display for HandHistory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.origin {
+            Origin::Seed(seed) => writeln!(f, "seed {}", seed)?,
+            Origin::Cards(cards) => {
+                let cards = cards.iter().map(|c| c.to_str()).collect::<Vec<_>>();
+                writeln!(f, "deck {}", cards.join(" "))?
+            }
+        }
+        for seat in &self.seats {
+            writeln!(f, "seat {}", escape_name(seat))?;
+        }
+        for area in &self.areas {
+            writeln!(f, "area {}", escape_name(area))?;
+        }
+        for entry in &self.entries {
+            match entry {
+                Entry::Deal { seat, card } => writeln!(f, "deal {} {}", seat, card.to_str())?,
+                Entry::Move { from, to, card } => writeln!(
+                    f,
+                    "move {} {} {}",
+                    zone_to_str(*from),
+                    zone_to_str(*to),
+                    card.to_str()
+                )?,
+                Entry::Bet { seat, amount } => writeln!(f, "bet {} {}", seat, amount)?,
+                Entry::Win { seat, amount } => writeln!(f, "win {} {}", seat, amount)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for HandHistory {
+    type Err = &'static str;
+
+    /// Reads a history written by `to_string()`
+    fn from_str(s: &str) -> Result<HandHistory, &'static str> {
+        let mut origin = None;
+        let mut history = HandHistory::new(Origin::Cards(Vec::new()), &[]);
+        for line in s.lines().map(|l| l.trim()) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let args = rest.split_whitespace().collect::<Vec<_>>();
+            match (keyword, args.as_slice()) {
+                ("seed", [seed]) => {
+                    origin = Some(Origin::Seed(seed.parse().map_err(|_| "Invalid seed")?));
+                }
+                ("deck", cards) => {
+                    let cards = cards
+                        .iter()
+                        .map(|c| Card::from_str(c))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    origin = Some(Origin::Cards(cards));
+                }
+                ("seat", _) => history.seats.push(unescape_name(rest)?),
+                ("area", _) => history.areas.push(unescape_name(rest)?),
+                ("deal", [seat, card]) => history.entries.push(Entry::Deal {
+                    seat: parse_number(seat)?,
+                    card: Card::from_str(card)?,
+                }),
+                ("move", [from, to, card]) => history.entries.push(Entry::Move {
+                    from: zone_from_str(from)?,
+                    to: zone_from_str(to)?,
+                    card: Card::from_str(card)?,
+                }),
+                ("bet", [seat, amount]) => history.entries.push(Entry::Bet {
+                    seat: parse_number(seat)?,
+                    amount: parse_number(amount)?,
+                }),
+                ("win", [seat, amount]) => history.entries.push(Entry::Win {
+                    seat: parse_number(seat)?,
+                    amount: parse_number(amount)?,
+                }),
+                _ => return Err("Invalid line"),
+            }
+        }
+        history.origin = origin.ok_or("No seed or deck")?;
+        Ok(history)
+    }
+}
+
+/// A `Recorder` plays a hand on a table and records everything that happens to it.
+pub struct Recorder {
+    table: Table,
+    history: HandHistory,
+}
+
+impl Recorder {
+    /// Creates a table for the seats with the deck ordered by the origin
+    pub fn new(origin: Origin, seats: &[&str]) -> Recorder {
+        let history = HandHistory::new(origin, seats);
+        Recorder {
+            table: history.table(),
+            history,
+        }
+    }
+
+    /// Returns the table as it is now
+    pub fn table(&self) -> &Table {
+        &self.table
+    }
+
+    /// Returns what has been recorded so far
+    pub fn history(&self) -> &HandHistory {
+        &self.history
+    }
+
+    /// Stops recording and returns the history
+    pub fn finish(self) -> HandHistory {
+        self.history
+    }
+
+    /// Adds a shared area to the table and returns its zone
+    pub fn add_area(&mut self, name: &str) -> Zone {
+        if let Some(zone) = self.table.find_area(name) {
+            return zone;
+        }
+        self.history.areas.push(name.to_string());
+        self.table.add_area(name)
+    }
+
+    /// Deals the top card of the deck to the seat
+    pub fn deal_to(&mut self, seat: usize) -> Result<Card, &'static str> {
+        let card = self.table.draw(Zone::Hand(seat))?;
+        self.history.entries.push(Entry::Deal { seat, card });
+        Ok(card)
+    }
+
+    /// Deals cards one at a time to each seat in turn until every seat has received
+    /// `numcards` or the deck runs out. Returns the number of cards dealt.
+    pub fn deal(&mut self, numcards: usize) -> usize {
+        let mut dealt = 0;
+        for _ in 0..numcards {
+            for seat in 0..self.table.seats.len() {
+                if self.deal_to(seat).is_err() {
+                    return dealt;
+                }
+                dealt += 1;
+            }
+        }
+        dealt
+    }
+
+    /// Moves the card from one zone to another
+    pub fn move_card(&mut self, from: Zone, to: Zone, card: &Card) -> Result<(), &'static str> {
+        self.table.move_card(from, to, card)?;
+        self.history.entries.push(Entry::Move {
+            from,
+            to,
+            card: *card,
+        });
+        Ok(())
+    }
+
+    /// Records the seat putting the amount into the pot
+    pub fn bet(&mut self, seat: usize, amount: u64) -> Result<(), &'static str> {
+        if seat >= self.table.seats.len() {
+            return Err("No such seat");
+        }
+        self.history.entries.push(Entry::Bet { seat, amount });
+        Ok(())
+    }
+
+    /// Records the seat winning the amount from the pot
+    pub fn win(&mut self, seat: usize, amount: u64) -> Result<(), &'static str> {
+        if seat >= self.table.seats.len() {
+            return Err("No such seat");
+        }
+        self.history.entries.push(Entry::Win { seat, amount });
+        Ok(())
+    }
+}
+
+/// Escapes a name so it fits on one line and keeps the spaces at its ends
+fn escape_name(name: &str) -> String {
+    let last = name.chars().count().saturating_sub(1);
+    let mut escaped = String::with_capacity(name.len());
+    for (i, c) in name.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ' ' if i == 0 || i == last => escaped.push_str("\\s"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Reads a name written by `escape_name`
+fn unescape_name(s: &str) -> Result<String, &'static str> {
+    let mut name = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            name.push(c);
+            continue;
+        }
+        name.push(match chars.next() {
+            Some('\\') => '\\',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('s') => ' ',
+            _ => return Err("Invalid escape in name"),
+        });
+    }
+    Ok(name)
+}
+
+fn parse_number<T: FromStr>(s: &str) -> Result<T, &'static str> {
+    s.parse().map_err(|_| "Invalid number")
+}
+
+fn zone_to_str(zone: Zone) -> String {
+    match zone {
+        Zone::Hand(seat) => format!("hand:{}", seat),
+        Zone::Stock => "stock".to_string(),
+        Zone::Discard => "discard".to_string(),
+        Zone::Board => "board".to_string(),
+        Zone::Area(area) => format!("area:{}", area),
+    }
+}
+
+fn zone_from_str(s: &str) -> Result<Zone, &'static str> {
+    match s.split_once(':') {
+        Some(("hand", seat)) => Ok(Zone::Hand(parse_number(seat)?)),
+        Some(("area", area)) => Ok(Zone::Area(parse_number(area)?)),
+        None if s == "stock" => Ok(Zone::Stock),
+        None if s == "discard" => Ok(Zone::Discard),
+        None if s == "board" => Ok(Zone::Board),
+        _ => Err("Invalid zone"),
+    }
+}
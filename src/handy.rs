@@ -21,6 +21,13 @@ pub trait Handy<C /*: Cards<C> = Self*/> {
     {
         self.cards().len()
     }
+    /// Tests if there are no cards
+    fn is_empty(&self) -> bool
+    where
+        Self: Cards<C> + Sized,
+    {
+        self.cards().is_empty()
+    }
     fn clear(&mut self);
     fn remove(&mut self, index: usize) -> C;
     fn remove_cards(&mut self, cards: &[C]);
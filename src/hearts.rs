@@ -0,0 +1,335 @@
+//! Hearts for four players.
+//!
+//! Every player is dealt 13 cards and the holder of the Two of Clubs leads the first trick.
+//! Players must follow the suit that was led if they can, and the highest card of that suit wins
+//! the trick and leads the next. Each Heart taken scores a point and the Queen of Spades scores
+//! 13, unless one player takes all of them and "shoots the moon", when everyone else scores 26.
+//! Hearts cannot be led until one has been played, and no points may be played to the first
+//! trick unless a player has nothing else. The game ends when a player reaches 100 points and
+//! the lowest score wins. Cards are not passed before each hand.
+
+#[cfg(feature = "std")]
+use rand::Rng;
+
+#[cfg(feature = "std")]
+use super::determinize::Constraint;
+use super::game::{Game, Table, Transition, Turn, Zone};
+#[cfg(feature = "std")]
+use super::mcts::{self, Playable};
+use super::*;
+
+/// The number of players
+pub const SEATS: usize = 4;
+
+/// The score which ends the game
+pub const GAME_OVER_AT: usize = 100;
+
+/// The tricks taken by each seat during the hand, face down
+pub const TAKEN: [Zone; SEATS] = [Zone::Area(0), Zone::Area(1), Zone::Area(2), Zone::Area(3)];
+
+/// The phase of a hand
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Phase {
+    /// Cards are being played to tricks
+    Play,
+    /// Every trick has been played and the next hand can be dealt
+    HandOver,
+}
+
+/// The actions a player can take
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Plays the card to the trick
+    Play(Card),
+    /// Deals the next hand
+    Deal,
+}
+
+/// The rules of Hearts, which also track the trick in progress and the scores
+#[derive(Clone, Debug, Default)]
+pub struct Hearts {
+    /// The seats and cards played to the trick in progress, in the order they were played. The
+    /// cards are also on the board.
+    pub trick: Vec<(usize, Card)>,
+    /// The last trick that was completed
+    pub last_trick: Vec<(usize, Card)>,
+    /// Whether a Heart has been played this hand
+    pub hearts_broken: bool,
+    /// The suits each seat has shown it holds none of this hand, by not following suit
+    pub voids: [Vec<Suit>; SEATS],
+    /// The score of each seat at the end of the last hand
+    pub scores: [usize; SEATS],
+    /// The seed of the next shuffle
+    pub seed: u64,
+}
+
+/// Returns the points the card scores for the player who takes it
+pub fn points(card: &Card) -> usize {
+    match (card.rank, card.suit) {
+        (_, Suit::Hearts) => 1,
+        (Rank::Queen, Suit::Spades) => 13,
+        _ => 0,
+    }
+}
+
+/// Returns the seat which wins the trick, which is the highest card of the suit led
+pub fn trick_winner(trick: &[(usize, Card)]) -> Option<usize> {
+    let led = trick.first()?.1.suit;
+    trick
+        .iter()
+        .filter(|(_, card)| card.suit == led)
+        .max_by(|a, b| a.1.rank.cmp(&b.1.rank))
+        .map(|(seat, _)| *seat)
+}
+
+impl Hearts {
+    /// Starts a game with the seats named South, West, North and East, in the order they play,
+    /// and deals the first hand with a deck shuffled by the seed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deckofcards::hearts::{Action, Hearts};
+    /// use deckofcards::{card, Cards};
+    ///
+    /// let mut game = Hearts::deal(1);
+    /// let leader = game.turn().seat;
+    /// assert!(game.table.seats[leader].hand.cards().contains(&card!("2C")));
+    /// assert!(game.act(leader, &Action::Play(card!("2C"))).is_ok());
+    /// ```
+    pub fn deal(seed: u64) -> Game<Hearts> {
+        let mut table = Table::new(Deck::new(), &["South", "West", "North", "East"]);
+        for seat in 0..SEATS {
+            let name = format!("Taken by {}", table.seats[seat].name);
+            let _ = table.add_area(&name);
+        }
+        let rules = Hearts {
+            seed,
+            ..Hearts::default()
+        };
+        let mut game = Game::new(table, rules).unwrap();
+        game.act(0, &Action::Deal).unwrap();
+        game
+    }
+
+    /// Returns the cards the seat may play now
+    pub fn legal_plays(&self, table: &Table, seat: usize) -> Vec<Card> {
+        let hand = table.seats[seat].hand.cards();
+        let first_trick = TAKEN
+            .iter()
+            .all(|z| table.cards(*z).unwrap_or_default().is_empty());
+        let two_of_clubs = Card::new(Rank::Two, Suit::Clubs);
+        let allowed = match self.trick.first() {
+            None if first_trick && hand.contains(&two_of_clubs) => vec![two_of_clubs],
+            None if !self.hearts_broken => cards_of_suit_except(hand, Suit::Hearts),
+            None => hand.to_vec(),
+            Some((_, led)) => {
+                let following = cards_of_suit(hand, led.suit);
+                if following.is_empty() && first_trick {
+                    hand.iter().filter(|c| points(c) == 0).copied().collect()
+                } else {
+                    following
+                }
+            }
+        };
+        if allowed.is_empty() {
+            hand.to_vec()
+        } else {
+            allowed
+        }
+    }
+
+    /// Returns the points each seat has taken so far this hand
+    pub fn hand_points(table: &Table) -> [usize; SEATS] {
+        let mut points = [0; SEATS];
+        for (seat, zone) in TAKEN.iter().enumerate() {
+            points[seat] = table
+                .cards(*zone)
+                .unwrap_or_default()
+                .iter()
+                .map(self::points)
+                .sum();
+        }
+        points
+    }
+
+    /// Returns the seats with the lowest score
+    pub fn leaders(&self) -> Vec<usize> {
+        let lowest = self.scores.iter().min().copied().unwrap_or(0);
+        (0..SEATS).filter(|s| self.scores[*s] == lowest).collect()
+    }
+
+    /// Adds the points taken in the hand to the scores
+    fn score_hand(&mut self, table: &Table) {
+        let points = Hearts::hand_points(table);
+        let moon = points.iter().position(|p| *p == 26);
+        for (seat, score) in self.scores.iter_mut().enumerate() {
+            *score += match moon {
+                Some(shooter) if shooter == seat => 0,
+                Some(_) => 26,
+                None => points[seat],
+            };
+        }
+    }
+}
+
+fn cards_of_suit_except(cards: &[Card], suit: Suit) -> Vec<Card> {
+    cards.iter().filter(|c| c.suit != suit).copied().collect()
+}
+
+impl game::Rules for Hearts {
+    type Card = Card;
+    type Hand = Hand;
+    type Phase = Phase;
+    type Action = Action;
+
+    fn initial_phase(&self) -> Phase {
+        Phase::HandOver
+    }
+
+    fn validate(
+        &self,
+        table: &Table,
+        turn: &Turn<Phase>,
+        action: &Action,
+    ) -> Result<(), &'static str> {
+        match (turn.phase, action) {
+            (Phase::Play, Action::Play(card)) => {
+                if !table.seats[turn.seat].hand.cards().contains(card) {
+                    Err("Card is not in hand")
+                } else if !self.legal_plays(table, turn.seat).contains(card) {
+                    Err("Card cannot be played now")
+                } else {
+                    Ok(())
+                }
+            }
+            (Phase::HandOver, Action::Deal) => Ok(()),
+            (Phase::Play, Action::Deal) => Err("The hand is not over"),
+            (Phase::HandOver, Action::Play(_)) => Err("The hand is over"),
+        }
+    }
+
+    fn apply(
+        &mut self,
+        table: &mut Table,
+        turn: &Turn<Phase>,
+        action: &Action,
+    ) -> Result<Transition<Phase>, &'static str> {
+        let card = match action {
+            Action::Play(card) => *card,
+            Action::Deal => {
+                for zone in TAKEN.iter().chain(&[Zone::Board]) {
+                    table.area_mut(*zone).unwrap().clear();
+                }
+                table.deck = Deck::new();
+                table.deck.seeded_shuffle(self.seed);
+                self.seed = self.seed.wrapping_add(1);
+                let _ = table.deal(13);
+                self.trick.clear();
+                self.last_trick.clear();
+                self.hearts_broken = false;
+                self.voids = Default::default();
+                let two_of_clubs = Card::new(Rank::Two, Suit::Clubs);
+                let leader = (0..SEATS)
+                    .find(|s| table.seats[*s].hand.cards().contains(&two_of_clubs))
+                    .unwrap();
+                return Ok(Transition::Seat(leader, Phase::Play));
+            }
+        };
+
+        table.move_card(Zone::Hand(turn.seat), Zone::Board, &card)?;
+        if let Some((_, led)) = self.trick.first() {
+            let voids = &mut self.voids[turn.seat];
+            if card.suit != led.suit && !voids.contains(&led.suit) {
+                voids.push(led.suit);
+            }
+        }
+        self.trick.push((turn.seat, card));
+        if card.suit == Suit::Hearts {
+            self.hearts_broken = true;
+        }
+        if self.trick.len() < SEATS {
+            return Ok(Transition::NextSeat(Phase::Play));
+        }
+
+        let winner = trick_winner(&self.trick).unwrap();
+        for (_, card) in &self.trick {
+            table.move_card(Zone::Board, TAKEN[winner], card)?;
+        }
+        self.last_trick = core::mem::take(&mut self.trick);
+        if !table.seats[winner].hand.is_empty() {
+            return Ok(Transition::Seat(winner, Phase::Play));
+        }
+        self.score_hand(table);
+        if self.scores.iter().any(|s| *s >= GAME_OVER_AT) {
+            Ok(Transition::GameOver)
+        } else {
+            Ok(Transition::Seat(0, Phase::HandOver))
+        }
+    }
+
+    /// Tricks are played face up, so everyone sees the trick being played and the tricks taken
+    fn is_public(&self, _turn: &Turn<Phase>, zone: Zone, _index: usize) -> bool {
+        zone == Zone::Board || TAKEN.contains(&zone)
+    }
+}
+
+/// Searches a hand of hearts, which is over once every trick has been played
+#[cfg(feature = "std")]
+impl Playable for Game<Hearts> {
+    type Action = Action;
+
+    fn players(&self) -> usize {
+        SEATS
+    }
+
+    fn to_move(&self) -> Option<usize> {
+        match self.turn().phase {
+            Phase::Play if !self.is_over() => Some(self.turn().seat),
+            _ => None,
+        }
+    }
+
+    fn legal_actions(&self) -> Vec<Action> {
+        match self.to_move() {
+            Some(seat) => self
+                .rules
+                .legal_plays(&self.table, seat)
+                .into_iter()
+                .map(Action::Play)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn apply(&mut self, action: &Action) {
+        let seat = self.turn().seat;
+        self.act(seat, action).expect("A legal action was refused");
+    }
+
+    /// Rewards each seat by the points it has avoided taking this hand, and shooting the moon
+    /// as the best result
+    fn rewards(&self) -> Vec<f64> {
+        let points = Hearts::hand_points(&self.table);
+        match points.iter().position(|p| *p == 26) {
+            Some(shooter) => (0..SEATS)
+                .map(|s| if s == shooter { 1.0 } else { 0.0 })
+                .collect(),
+            None => points.iter().map(|p| 1.0 - *p as f64 / 26.0).collect(),
+        }
+    }
+
+    /// Deals the unseen cards so that a player who did not follow suit this hand holds none of
+    /// the suit led
+    fn determinize<R: Rng>(&self, seat: usize, rng: &mut R) -> Option<Self> {
+        let constraints = (0..SEATS)
+            .filter(|player| *player != seat)
+            .flat_map(|player| {
+                self.rules.voids[player]
+                    .iter()
+                    .map(move |suit| Constraint::Void(Zone::Hand(player), *suit))
+            })
+            .collect::<Vec<_>>();
+        mcts::determinized(self, seat, &constraints, rng)
+    }
+}
@@ -0,0 +1,366 @@
+//! Undo and redo for decks and hands.
+//!
+//! A `History` wraps a `Deck` or `Hand` and records every change made through it as an event
+//! which can be reverted. Changes can be undone and redone without limit, and the current state
+//! can be rebuilt by replaying the events from the initial state.
+
+use core::ops::Deref;
+
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg32;
+
+use super::*;
+
+/// Something which can have events applied and reverted
+pub trait Undoable {
+    /// The event describing a change
+    type Event: Clone;
+
+    /// Applies the event
+    fn apply(&mut self, event: &Self::Event);
+
+    /// Reverts the event, which must be the last event that was applied
+    fn revert(&mut self, event: &Self::Event);
+}
+
+/// A change made to a `Deck`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DeckEvent<C> {
+    /// The card was dealt from the top of the undealt pile
+    Deal(C),
+    /// The cards were added to the undealt pile
+    Push(Vec<C>),
+    /// The dealt cards were returned to the undealt pile
+    Reset(Vec<C>),
+    /// The undealt pile was shuffled or sorted
+    Reorder {
+        /// The undealt cards before
+        before: Vec<C>,
+        /// The undealt cards after
+        after: Vec<C>,
+    },
+}
+
+impl<C: Clone> Undoable for Deck<C> {
+    type Event = DeckEvent<C>;
+
+    fn apply(&mut self, event: &DeckEvent<C>) {
+        match event {
+            DeckEvent::Deal(_) => {
+                if let Some(card) = self.cards.pop() {
+                    self.dealt_cards.push(card);
+                }
+            }
+            DeckEvent::Push(cards) => self.cards.extend_from_slice(cards),
+            DeckEvent::Reset(_) => {
+                self.cards.extend(self.dealt_cards.drain(..).rev());
+            }
+            DeckEvent::Reorder { after, .. } => self.cards = after.clone(),
+        }
+    }
+
+    fn revert(&mut self, event: &DeckEvent<C>) {
+        match event {
+            DeckEvent::Deal(_) => {
+                if let Some(card) = self.dealt_cards.pop() {
+                    self.cards.push(card);
+                }
+            }
+            DeckEvent::Push(cards) => {
+                self.cards.truncate(self.cards.len() - cards.len());
+            }
+            DeckEvent::Reset(dealt) => {
+                self.cards.truncate(self.cards.len() - dealt.len());
+                self.dealt_cards = dealt.clone();
+            }
+            DeckEvent::Reorder { before, .. } => self.cards = before.clone(),
+        }
+    }
+}
+
+/// A change made to a `Hand`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HandEvent {
+    /// The cards were added to the end of the hand
+    Push(Vec<Card>),
+    /// The cards were removed from the hand, one after the other, from the given indices
+    Remove(Vec<(usize, Card)>),
+    /// The hand was emptied of these cards
+    Clear(Vec<Card>),
+    /// The hand was shuffled or sorted
+    Reorder {
+        /// The cards before
+        before: Vec<Card>,
+        /// The cards after
+        after: Vec<Card>,
+    },
+}
+
+impl Undoable for Hand {
+    type Event = HandEvent;
+
+    fn apply(&mut self, event: &HandEvent) {
+        match event {
+            HandEvent::Push(cards) => self.push_cards(cards),
+            HandEvent::Remove(removed) => {
+                for (index, _) in removed {
+                    let _ = self.cards.remove(*index);
+                }
+            }
+            HandEvent::Clear(_) => self.cards.clear(),
+            HandEvent::Reorder { after, .. } => self.cards = after.clone(),
+        }
+    }
+
+    fn revert(&mut self, event: &HandEvent) {
+        match event {
+            HandEvent::Push(cards) => {
+                let len = self.cards.len() - cards.len();
+                self.cards.truncate(len);
+            }
+            HandEvent::Remove(removed) => {
+                for (index, card) in removed.iter().rev() {
+                    self.cards.insert(*index, *card);
+                }
+            }
+            HandEvent::Clear(cards) => self.cards = cards.clone(),
+            HandEvent::Reorder { before, .. } => self.cards = before.clone(),
+        }
+    }
+}
+
+/// A `History` records every change made to a deck or hand so that changes can be undone and
+/// redone. It dereferences to the current state for reading.
+///
+/// # Example
+///
+/// ```
+/// use deckofcards::history::History;
+/// use deckofcards::{Card, Deck, Decky};
+///
+/// let mut deck = History::new(Deck::<Card>::new());
+/// let card = deck.deal_one().unwrap();
+/// assert_eq!(deck.dealt_count(), 1);
+/// deck.undo();
+/// assert_eq!(deck.top_card(), Some(card));
+/// ```
+#[derive(Clone)]
+pub struct History<T: Undoable> {
+    initial: T,
+    current: T,
+    done: Vec<T::Event>,
+    undone: Vec<T::Event>,
+}
+
+impl<T: Undoable> Deref for History<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.current
+    }
+}
+
+impl<T: Undoable + Clone> History<T> {
+    /// Starts recording changes from the initial state
+    pub fn new(initial: T) -> Self {
+        History {
+            current: initial.clone(),
+            initial,
+            done: Vec::new(),
+            undone: Vec::new(),
+        }
+    }
+
+    /// Returns the state recording started from
+    pub fn initial(&self) -> &T {
+        &self.initial
+    }
+
+    /// Returns the events that have been applied, oldest first
+    pub fn events(&self) -> &[T::Event] {
+        self.done.as_slice()
+    }
+
+    /// Applies the event and records it. Anything which was undone can no longer be redone.
+    pub fn record(&mut self, event: T::Event) {
+        self.current.apply(&event);
+        self.done.push(event);
+        self.undone.clear();
+    }
+
+    /// Tests if there is anything to undo
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    /// Tests if there is anything to redo
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    /// Reverts the last event. Returns false if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        if let Some(event) = self.done.pop() {
+            self.current.revert(&event);
+            self.undone.push(event);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Applies the last event that was undone. Returns false if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        if let Some(event) = self.undone.pop() {
+            self.current.apply(&event);
+            self.done.push(event);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Rebuilds the current state by applying every event to the initial state
+    pub fn replay(&self) -> T {
+        let mut state = self.initial.clone();
+        self.done.iter().for_each(|event| state.apply(event));
+        state
+    }
+
+    /// Stops recording and returns the current state
+    pub fn into_inner(self) -> T {
+        self.current
+    }
+}
+
+impl<C: Clone> History<Deck<C>> {
+    /// Deals the card from the undealt pile. If there are no cards left, the function
+    /// will return an error.
+    pub fn deal_one(&mut self) -> Result<C, &'static str> {
+        let card = self.current.cards.last().cloned().ok_or("No cards left")?;
+        self.record(DeckEvent::Deal(card.clone()));
+        Ok(card)
+    }
+
+    /// Deals one or more card from the undealt pile, recording each card as its own event
+    pub fn deal(&mut self, numcards: usize) -> Vec<C> {
+        (0..numcards).map_while(|_| self.deal_one().ok()).collect()
+    }
+
+    /// Add zero or more cards to the undealt part of the deck
+    pub fn push(&mut self, cards: &[C]) {
+        self.record(DeckEvent::Push(cards.to_vec()));
+    }
+
+    /// Return the dealt cards back to the end of the undealt pile
+    pub fn reset(&mut self) {
+        self.record(DeckEvent::Reset(self.current.dealt_cards.clone()));
+    }
+
+    /// Reorders the undealt cards with the function, e.g. to sort them
+    pub fn reorder<F: FnOnce(&mut [C])>(&mut self, f: F) {
+        let before = self.current.cards.clone();
+        let mut after = before.clone();
+        f(&mut after);
+        self.record(DeckEvent::Reorder { before, after });
+    }
+
+    /// Perform a Knuth shuffle with the given RNG
+    pub fn knuth_shuffle<R: Rng>(&mut self, rng: &mut R) {
+        self.reorder(|cards| cards::knuth_shuffle(cards, rng));
+    }
+
+    /// Shuffle the cards into a random order
+    #[cfg(feature = "std")]
+    pub fn shuffle(&mut self) {
+        self.knuth_shuffle(&mut rand::thread_rng());
+    }
+
+    /// Shuffle the cards into a random but predictable order
+    pub fn seeded_shuffle(&mut self, seed: u64) {
+        self.knuth_shuffle(&mut Pcg32::seed_from_u64(seed));
+    }
+}
+
+impl History<Hand> {
+    /// Adds one `Card` to the `Hand`
+    pub fn push_card(&mut self, card: Card) {
+        self.record(HandEvent::Push(vec![card]));
+    }
+
+    /// Adds zero or more cards to the `Hand`
+    pub fn push_cards(&mut self, cards: &[Card]) {
+        self.record(HandEvent::Push(cards.to_vec()));
+    }
+
+    /// Adds zero or more cards from some other `Hand`
+    pub fn push_hand(&mut self, other: &Hand) {
+        self.push_cards(other.cards());
+    }
+
+    /// Clears the `Hand` (makes it empty)
+    pub fn clear(&mut self) {
+        self.record(HandEvent::Clear(self.current.cards.clone()));
+    }
+
+    /// Removes a `Card` from the `Hand` and returns it, panics if index does not exist
+    pub fn remove(&mut self, index: usize) -> Card {
+        let card = self.current.cards[index];
+        self.record(HandEvent::Remove(vec![(index, card)]));
+        card
+    }
+
+    /// Removes first instance of the matching card from the `Hand`
+    pub fn remove_card(&mut self, card: &Card) -> bool {
+        self.remove_matching(core::slice::from_ref(card), false)
+    }
+
+    /// Removes the first instance of every matching card from the `Hand`
+    pub fn remove_cards(&mut self, cards: &[Card]) {
+        let _ = self.remove_matching(cards, false);
+    }
+
+    /// Removes the every instance of every matching card from the `Hand`
+    pub fn remove_all_cards(&mut self, cards: &[Card]) {
+        let _ = self.remove_matching(cards, true);
+    }
+
+    /// Reorders the cards with the function, e.g. to sort them
+    pub fn reorder<F: FnOnce(&mut [Card])>(&mut self, f: F) {
+        let before = self.current.cards.clone();
+        let mut after = before.clone();
+        f(&mut after);
+        self.record(HandEvent::Reorder { before, after });
+    }
+
+    /// Shuffle the cards into a random order
+    #[cfg(feature = "std")]
+    pub fn shuffle(&mut self) {
+        self.reorder(|cards| cards::knuth_shuffle(cards, &mut rand::thread_rng()));
+    }
+
+    /// Shuffle the cards into a random but predictable order
+    pub fn seeded_shuffle(&mut self, seed: u64) {
+        let mut rng = Pcg32::seed_from_u64(seed);
+        self.reorder(|cards| cards::knuth_shuffle(cards, &mut rng));
+    }
+
+    /// Records the removal of matching cards as a single event. Returns false if nothing matched.
+    fn remove_matching(&mut self, cards: &[Card], every: bool) -> bool {
+        let mut remaining = self.current.cards.clone();
+        let mut removed = Vec::new();
+        for card in cards {
+            while let Some(index) = remaining.iter().position(|c| c == card) {
+                removed.push((index, remaining.remove(index)));
+                if !every {
+                    break;
+                }
+            }
+        }
+        if removed.is_empty() {
+            return false;
+        }
+        self.record(HandEvent::Remove(removed));
+        true
+    }
+}
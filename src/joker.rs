@@ -1,9 +1,9 @@
-use std::fmt;
+use core::fmt;
 
-use crate::{
-    suit::{self, Color},
-    DisplayCard,
-};
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+
+use crate::{suit::Color, DisplayCard};
 
 #[derive(Copy, Clone, Debug, PartialEq, Default)]
 pub struct Joker {
@@ -25,8 +25,8 @@ impl DisplayCard for Joker {
         "JK".to_string()
     }
 
-    fn from_str(s: &str) -> std::prelude::v1::Result<Joker, &'static str> {
-        if s.len() < 1 || s.len() > 3 {
+    fn from_str(s: &str) -> core::result::Result<Joker, &'static str> {
+        if s.is_empty() || s.len() > 3 {
             return Err("String is wrong length");
         }
 
@@ -39,18 +39,14 @@ impl DisplayCard for Joker {
         let mut c2_3 = c2.to_string();
         c2_3.push(c3);
 
-        if c1.eq_ignore_ascii_case(&'B') {
-            if c2_3.eq_ignore_ascii_case("JK") {
-                return Ok(Joker {
-                    color: Color::Black,
-                });
-            }
+        if c1.eq_ignore_ascii_case(&'B') && c2_3.eq_ignore_ascii_case("JK") {
+            return Ok(Joker {
+                color: Color::Black,
+            });
         }
 
-        if c1.eq_ignore_ascii_case(&'R') {
-            if c2_3.eq_ignore_ascii_case("JK") {
-                return Ok(Joker { color: Color::Red });
-            }
+        if c1.eq_ignore_ascii_case(&'R') && c2_3.eq_ignore_ascii_case("JK") {
+            return Ok(Joker { color: Color::Red });
         }
 
         if c1.to_string().eq_ignore_ascii_case("JK") {
@@ -62,22 +58,35 @@ impl DisplayCard for Joker {
         Err("Invalid string")
     }
 
+    /// Returns "JK" colored by the current `Theme`
     #[cfg(feature = "pretty")]
     fn to_pretty(&self) -> String {
-        use colored::Colorize;
-
-        use crate::GRAY;
-
-        format!(
-            "{}",
-            match self.color {
-                suit::Color::Black => "JK".to_string().as_str().custom_color(GRAY),
-                suit::Color::Red => "JK".to_string().as_str().red(),
-            }
-        )
+        crate::theme::Theme::current().pretty_joker(self)
     }
 
     fn name(&self) -> String {
         "Joker".to_string()
     }
+
+    /// Returns the character for the joker from the Unicode Playing Cards block, '🂿' for red
+    /// and '🃏' for black
+    fn to_unicode_glyph(&self) -> Option<char> {
+        match self.color {
+            Color::Red => Some('\u{1F0BF}'),
+            Color::Black => Some('\u{1F0CF}'),
+        }
+    }
+
+    /// Creates a joker from a character in the Unicode Playing Cards block. The white joker
+    /// '🃟' is treated as the default joker.
+    fn from_unicode_glyph(ch: char) -> Result<Joker, &'static str> {
+        match ch {
+            '\u{1F0BF}' => Ok(Joker { color: Color::Red }),
+            '\u{1F0CF}' => Ok(Joker {
+                color: Color::Black,
+            }),
+            '\u{1F0DF}' => Ok(Joker::default()),
+            _ => Err("Not a joker glyph"),
+        }
+    }
 }
@@ -1,5 +1,9 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
 /// Creates a `Card` and sets its rank / suit from its abbreviated string description. The description
-/// is of the form "RS", Rank followed by Suit, e.g. "2D" for Two of Diamonds.
+/// is of the form "RS", Rank followed by Suit, e.g. "2D" for Two of Diamonds. A string literal is
+/// checked when compiling and makes a const expression, anything else is parsed when it runs and
+/// panics if it is not a card.
 ///
 /// # Examples
 ///
@@ -8,20 +12,28 @@
 /// ```
 /// # #[macro_use] extern crate deckofcards;
 /// # fn main() {
+/// const ACE_OF_SPADES: deckofcards::Card = card!("AS");
 /// let card = card!("AS");
 /// # }
 /// ```
+///
+/// An unknown card does not compile
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate deckofcards;
+/// # fn main() {
+/// let card = card!("ZZ");
+/// # }
+/// ```
 #[macro_export]
 macro_rules! card {
-    ($s:expr) => {{
-        let cr = $crate::Card::from_str($s);
-        cr.unwrap_or_else(|_| {
-            panic!("Not a known card {}", $s);
-        })
-    }};
+    ($($s:tt)+) => {
+        $crate::__card!($crate; $($s)+)
+    };
 }
 
-/// Creates a `Hand` of cards from the list of abbreviated cards string specified by rank / suit,
+/// Creates a `Hand` of cards from the list of abbreviated cards string specified by rank / suit.
+/// String literals are checked when compiling.
 ///
 /// # Examples
 ///
@@ -33,22 +45,61 @@ macro_rules! card {
 /// let hand = hand!("QH", "2D");
 /// # }
 /// ```
+///
+/// A card that is not known does not compile
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate deckofcards;
+/// # fn main() {
+/// let hand = hand!("QH", "2X");
+/// # }
+/// ```
 #[macro_export]
 macro_rules! hand {
-    () => {
-        $crate::Hand::new()
+    ($($s:tt)*) => {
+        $crate::__hand!($crate; $($s)*)
     };
-    ( $( $s:expr ),* ) => {
-        {
-            let mut hand = $crate::Hand::new();
-            $(
-                hand += card!($s);
-            )*
-            hand
-        }
+}
+
+/// Creates a `Hand` like `hand!` and also checks when compiling that no card is listed twice.
+///
+/// # Examples
+///
+/// ```
+/// # #[macro_use] extern crate deckofcards;
+/// # fn main() {
+/// let hand = unique_hand!("QH", "2D");
+/// # }
+/// ```
+///
+/// A card listed twice does not compile
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate deckofcards;
+/// # fn main() {
+/// let hand = unique_hand!("QH", "2D", "QH");
+/// # }
+/// ```
+///
+/// The same card written another way is also caught
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate deckofcards;
+/// # fn main() {
+/// let hand = unique_hand!("QH", "2D", "HQ");
+/// # }
+/// ```
+#[macro_export]
+macro_rules! unique_hand {
+    ($($s:tt)*) => {
+        $crate::__unique_hand!($crate; $($s)*)
     };
 }
 
+// The macros above pass `$crate` to these, so the cards they make work however this crate is named
+#[doc(hidden)]
+pub use handy_derive::{card as __card, hand as __hand, unique_hand as __unique_hand};
+
 /// Creates a new `Hand` that is the combination two hands into one hand. This does not consume
 /// the original hands.
 ///
@@ -69,7 +120,7 @@ macro_rules! hand {
 macro_rules! combine_hands {
     ( $( $h: expr),* ) => {
         {
-            let mut result = $crate::Hand::new();
+            let mut result = <$crate::Hand as $crate::Handy<$crate::Card>>::new();
             $(
                 result += $h;
             )*
@@ -82,10 +133,21 @@ macro_rules! combine_hands {
 #[macro_export]
 macro_rules! deck {
     () => {
-        $crate::Deck::new()
+        <$crate::Deck<$crate::Card> as $crate::Decky<$crate::Card>>::new()
     };
 }
 
+extern crate alloc;
+
+// Without std, the parts of its prelude that come from alloc are imported here for every module
+#[cfg(not(feature = "std"))]
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
 #[cfg(feature = "pretty")]
 use colored::CustomColor;
 #[cfg(feature = "pretty")]
@@ -117,10 +179,66 @@ mod decky;
 pub use decky::Decky;
 
 mod hand;
-pub use hand::{Area, Hand};
+pub use hand::{Area, Duplicates, Hand, InvalidCard};
 
 mod handy;
 pub use handy::Handy;
 
+#[cfg(feature = "pretty")]
+pub mod art;
+
+#[cfg(feature = "games")]
+pub mod blackjack;
+
+#[cfg(feature = "capi")]
+pub mod capi;
+
+pub mod determinize;
+
+pub mod game;
+
+pub mod hand_history;
+
+#[cfg(feature = "games")]
+pub mod hearts;
+
+pub mod history;
+
+pub mod locale;
+
+#[cfg(feature = "std")]
+pub mod mcts;
+
+#[cfg(feature = "mental")]
+pub mod mental;
+
+pub mod notation;
+
+pub mod poker;
+
+pub mod pokerstars;
+
+#[cfg(feature = "python")]
+pub mod python;
+
+pub mod range;
+
+pub mod rummy;
+
+#[cfg(feature = "server")]
+pub mod server;
+
+#[cfg(feature = "games")]
+pub mod solitaire;
+
+#[cfg(feature = "svg")]
+pub mod svg;
+
+#[cfg(feature = "pretty")]
+pub mod theme;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 #[cfg(test)]
 mod tests;
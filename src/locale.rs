@@ -0,0 +1,237 @@
+//! Card names in languages other than English.
+//!
+//! A `Locale` formats ranks, suits and cards in its language and parses them back. Each locale has
+//! its own rank abbreviations, e.g. German uses B, D and K for Bube, Dame and König, so the short
+//! form of a card is the abbreviation followed by the suit symbol, e.g. "D♥". Parsing ignores case.
+
+use core::slice::Iter;
+
+use super::*;
+
+/// A language that card names can be written in
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+    Italian,
+    Dutch,
+}
+
+/// Rank names in the order of `Rank::ranks()`, indexed by `Locale::ordinal()`
+const RANK_NAMES: [[&str; 13]; 6] = [
+    [
+        "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten", "Jack", "Queen",
+        "King", "Ace",
+    ],
+    [
+        "Deux", "Trois", "Quatre", "Cinq", "Six", "Sept", "Huit", "Neuf", "Dix", "Valet", "Dame",
+        "Roi", "As",
+    ],
+    [
+        "Zwei", "Drei", "Vier", "Fünf", "Sechs", "Sieben", "Acht", "Neun", "Zehn", "Bube", "Dame",
+        "König", "Ass",
+    ],
+    [
+        "Dos", "Tres", "Cuatro", "Cinco", "Seis", "Siete", "Ocho", "Nueve", "Diez", "Jota",
+        "Reina", "Rey", "As",
+    ],
+    [
+        "Due", "Tre", "Quattro", "Cinque", "Sei", "Sette", "Otto", "Nove", "Dieci", "Fante",
+        "Donna", "Re", "Asso",
+    ],
+    [
+        "Twee", "Drie", "Vier", "Vijf", "Zes", "Zeven", "Acht", "Negen", "Tien", "Boer", "Vrouw",
+        "Heer", "Aas",
+    ],
+];
+
+/// Rank abbreviations in the order of `Rank::ranks()`, indexed by `Locale::ordinal()`
+const RANK_ABBREVIATIONS: [[&str; 13]; 6] = [
+    [
+        "2", "3", "4", "5", "6", "7", "8", "9", "T", "J", "Q", "K", "A",
+    ],
+    [
+        "2", "3", "4", "5", "6", "7", "8", "9", "10", "V", "D", "R", "A",
+    ],
+    [
+        "2", "3", "4", "5", "6", "7", "8", "9", "10", "B", "D", "K", "A",
+    ],
+    [
+        "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A",
+    ],
+    [
+        "2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A",
+    ],
+    [
+        "2", "3", "4", "5", "6", "7", "8", "9", "10", "B", "V", "H", "A",
+    ],
+];
+
+/// Suit names in the order of `Suit::suits()`, indexed by `Locale::ordinal()`
+const SUIT_NAMES: [[&str; 4]; 6] = [
+    ["Spades", "Hearts", "Diamonds", "Clubs"],
+    ["Pique", "Cœur", "Carreau", "Trèfle"],
+    ["Pik", "Herz", "Karo", "Kreuz"],
+    ["Picas", "Corazones", "Diamantes", "Tréboles"],
+    ["Picche", "Cuori", "Quadri", "Fiori"],
+    ["Schoppen", "Harten", "Ruiten", "Klaveren"],
+];
+
+impl Locale {
+    /// Returns an iterator through the locales
+    pub fn iterator() -> Iter<'static, Locale> {
+        static LOCALES: [Locale; 6] = [
+            Locale::English,
+            Locale::French,
+            Locale::German,
+            Locale::Spanish,
+            Locale::Italian,
+            Locale::Dutch,
+        ];
+        LOCALES.iter()
+    }
+
+    /// Returns an ordinal for the locale
+    pub fn ordinal(&self) -> usize {
+        match self {
+            Locale::English => 0,
+            Locale::French => 1,
+            Locale::German => 2,
+            Locale::Spanish => 3,
+            Locale::Italian => 4,
+            Locale::Dutch => 5,
+        }
+    }
+
+    /// Returns the locale for a language code such as "de" or "fr-CA"
+    pub fn from_code(code: &str) -> Result<Locale, &'static str> {
+        let language = code.split(['-', '_']).next().unwrap_or_default();
+        match language.to_ascii_lowercase().as_str() {
+            "en" => Ok(Locale::English),
+            "fr" => Ok(Locale::French),
+            "de" => Ok(Locale::German),
+            "es" => Ok(Locale::Spanish),
+            "it" => Ok(Locale::Italian),
+            "nl" => Ok(Locale::Dutch),
+            _ => Err("Unsupported language"),
+        }
+    }
+
+    /// Returns the two letter language code, e.g. "de"
+    pub fn code(&self) -> &'static str {
+        match self {
+            Locale::English => "en",
+            Locale::French => "fr",
+            Locale::German => "de",
+            Locale::Spanish => "es",
+            Locale::Italian => "it",
+            Locale::Dutch => "nl",
+        }
+    }
+
+    /// Returns the name of the rank, e.g. "Dame" for the Queen in German
+    pub fn rank_name(&self, rank: Rank) -> &'static str {
+        RANK_NAMES[self.ordinal()][rank.ordinal()]
+    }
+
+    /// Returns the abbreviation of the rank, e.g. "D" for the Queen in German
+    pub fn rank_abbreviation(&self, rank: Rank) -> &'static str {
+        RANK_ABBREVIATIONS[self.ordinal()][rank.ordinal()]
+    }
+
+    /// Returns the name of the suit, e.g. "Herz" for Hearts in German
+    pub fn suit_name(&self, suit: Suit) -> &'static str {
+        SUIT_NAMES[self.ordinal()][suit.ordinal()]
+    }
+
+    /// Returns the name of the card, e.g. "Dame de Cœur" in French or "Herz Dame" in German
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deckofcards::locale::Locale;
+    /// use deckofcards::card;
+    ///
+    /// assert_eq!(Locale::Spanish.card_name(&card!("AS")), "As de Picas");
+    /// assert_eq!(Locale::German.card_name(&card!("QH")), "Herz Dame");
+    /// ```
+    pub fn card_name(&self, card: &Card) -> String {
+        let rank = self.rank_name(card.rank);
+        let suit = self.suit_name(card.suit);
+        match self {
+            Locale::English => format!("{} of {}", rank, suit),
+            Locale::French | Locale::Spanish => format!("{} de {}", rank, suit),
+            Locale::Italian => format!("{} di {}", rank, suit),
+            Locale::German | Locale::Dutch => format!("{} {}", suit, rank),
+        }
+    }
+
+    /// Returns the short form of the card, the rank abbreviation followed by the suit symbol,
+    /// e.g. "D♥" for the Queen of Hearts in German
+    pub fn card_abbreviation(&self, card: &Card) -> String {
+        format!(
+            "{}{}",
+            self.rank_abbreviation(card.rank),
+            card.suit.to_unicode()
+        )
+    }
+
+    /// Parses a rank from its name or abbreviation
+    pub fn parse_rank(&self, s: &str) -> Result<Rank, &'static str> {
+        let s = s.trim();
+        Rank::iterator()
+            .find(|rank| {
+                matches(self.rank_name(**rank), s) || matches(self.rank_abbreviation(**rank), s)
+            })
+            .copied()
+            .ok_or("Invalid rank")
+    }
+
+    /// Parses a suit from its name or symbol
+    pub fn parse_suit(&self, s: &str) -> Result<Suit, &'static str> {
+        let s = s.trim();
+        Suit::iterator()
+            .find(|suit| matches(self.suit_name(**suit), s) || s == suit.to_unicode().to_string())
+            .copied()
+            .ok_or("Invalid suit")
+    }
+
+    /// Parses a card from its name or short form, as written by `card_name()` or
+    /// `card_abbreviation()`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deckofcards::locale::Locale;
+    /// use deckofcards::card;
+    ///
+    /// assert_eq!(Locale::German.parse_card("herz dame"), Ok(card!("QH")));
+    /// assert_eq!(Locale::German.parse_card("D♥"), Ok(card!("QH")));
+    /// assert_eq!(Locale::Dutch.parse_card("V♥"), Ok(card!("QH")));
+    /// ```
+    pub fn parse_card(&self, s: &str) -> Result<Card, &'static str> {
+        let s = s.trim();
+        if let Some(card) = Card::all_cards()
+            .iter()
+            .find(|card| matches(&self.card_name(card), s))
+        {
+            return Ok(*card);
+        }
+        let suit_char = s.chars().last().ok_or("Empty string")?;
+        let rank = &s[..s.len() - suit_char.len_utf8()];
+        if rank.is_empty() {
+            return Err("Invalid string");
+        }
+        let suit = Suit::from_unicode(suit_char).map_err(|_| "Invalid suit")?;
+        let rank = self.parse_rank(rank)?;
+        Ok(Card::new(rank, suit))
+    }
+}
+
+/// Compares the strings ignoring case, including letters outside of ASCII such as "Ö"
+fn matches(expected: &str, s: &str) -> bool {
+    expected.to_lowercase() == s.to_lowercase()
+}
@@ -0,0 +1,270 @@
+//! Monte Carlo tree search for picking moves in card games.
+//!
+//! The search plays the game out many times from the current position, picking moves at random
+//! once it leaves the tree, and grows a tree of the moves that did best. Where players cannot see
+//! each other's cards it is Information Set MCTS: each playout starts from a determinization, a
+//! deal of the unseen cards that agrees with what the searching seat knows, and the tree is built
+//! from actions rather than positions so it is shared by every determinization.
+//!
+//! Cards drawn after the search starts are part of the determinization too, so the tree does not
+//! branch on which card was drawn. The search is at its best in games where the cards are dealt
+//! before play, like Hearts.
+
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg32;
+
+use super::determinize::{Constraint, Sampler};
+use super::game::{Game, Rules};
+use super::*;
+
+/// A game the search can play
+pub trait Playable: Clone {
+    /// The actions a player can take
+    type Action: Clone + PartialEq;
+
+    /// Returns the number of players, which receive rewards
+    fn players(&self) -> usize;
+
+    /// Returns the seat that acts next, or `None` if the game is over
+    fn to_move(&self) -> Option<usize>;
+
+    /// Returns the actions the seat that acts next can take
+    fn legal_actions(&self) -> Vec<Self::Action>;
+
+    /// Takes one of the legal actions
+    fn apply(&mut self, action: &Self::Action);
+
+    /// Returns the reward of each seat from 0 to 1, e.g. 1 for a win and 0 for a loss. It is also
+    /// asked for when a playout is cut short, so it should then estimate how each seat is doing.
+    fn rewards(&self) -> Vec<f64>;
+
+    /// Returns a copy of the game with the cards the seat cannot see dealt at random, in a way
+    /// that agrees with what the seat knows, or `None` to skip the playout if no such deal can be
+    /// found. A game where everything can be seen returns a copy.
+    fn determinize<R: Rng>(&self, _seat: usize, _rng: &mut R) -> Option<Self> {
+        Some(self.clone())
+    }
+}
+
+/// How an action at the root of the search did
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistic<A> {
+    /// The action
+    pub action: A,
+    /// The number of playouts that started with the action
+    pub visits: usize,
+    /// The mean reward of those playouts for the seat taking the action
+    pub reward: f64,
+}
+
+/// The settings of a search
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mcts {
+    /// The number of playouts
+    pub iterations: usize,
+    /// How much the search favors actions it has tried less over actions that did well
+    pub exploration: f64,
+    /// The seed of the random numbers, so a search can be repeated
+    pub seed: u64,
+    /// The most random actions in a playout after leaving the tree
+    pub max_depth: usize,
+}
+
+impl Default for Mcts {
+    fn default() -> Self {
+        Mcts {
+            iterations: 1000,
+            exploration: core::f64::consts::SQRT_2,
+            seed: 0,
+            max_depth: 1000,
+        }
+    }
+}
+
+/// A node of the tree, which is reached by taking its action
+struct Node<A> {
+    action: Option<A>,
+    /// The seat which took the action
+    player: usize,
+    children: Vec<usize>,
+    visits: usize,
+    /// The number of times the node could have been chosen
+    availability: usize,
+    reward: f64,
+}
+
+impl<A> Node<A> {
+    fn new(action: Option<A>, player: usize) -> Node<A> {
+        Node {
+            action,
+            player,
+            children: Vec::new(),
+            visits: 0,
+            availability: 1,
+            reward: 0.0,
+        }
+    }
+}
+
+impl Mcts {
+    /// Returns the action the search thinks best for the seat to move, which is the one it tried
+    /// most, or `None` if the game is over
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "games")] {
+    /// use deckofcards::hearts::Hearts;
+    /// use deckofcards::mcts::{Mcts, Playable};
+    ///
+    /// let game = Hearts::deal(4);
+    /// let search = Mcts { iterations: 50, ..Mcts::default() };
+    /// let action = search.search(&game).unwrap();
+    /// assert!(game.legal_actions().contains(&action));
+    /// # }
+    /// ```
+    pub fn search<G: Playable>(&self, game: &G) -> Option<G::Action> {
+        self.evaluate(game).into_iter().next().map(|s| s.action)
+    }
+
+    /// Searches the game and returns how each action of the seat to move did, the most tried
+    /// first
+    pub fn evaluate<G: Playable>(&self, game: &G) -> Vec<Statistic<G::Action>> {
+        let seat = match game.to_move() {
+            Some(seat) => seat,
+            None => return Vec::new(),
+        };
+        let mut rng = Pcg32::seed_from_u64(self.seed);
+        let mut nodes = vec![Node::new(None, seat)];
+        for _ in 0..self.iterations {
+            let mut state = match game.determinize(seat, &mut rng) {
+                Some(state) => state,
+                None => continue,
+            };
+            let path = self.select(&mut nodes, &mut state, &mut rng);
+            let rewards = self.playout(&mut state, &mut rng);
+            for n in path {
+                let node = &mut nodes[n];
+                node.visits += 1;
+                node.reward += rewards.get(node.player).copied().unwrap_or(0.0);
+            }
+        }
+
+        let mut statistics = nodes[0]
+            .children
+            .iter()
+            .map(|c| {
+                let node = &nodes[*c];
+                Statistic {
+                    action: node.action.clone().unwrap(),
+                    visits: node.visits,
+                    reward: node.reward / node.visits.max(1) as f64,
+                }
+            })
+            .collect::<Vec<_>>();
+        statistics.sort_by_key(|s| core::cmp::Reverse(s.visits));
+        statistics
+    }
+
+    /// Walks down the tree taking the best action available in this determinization, until it
+    /// reaches an action that has not been tried, and adds that to the tree. Returns the nodes
+    /// visited.
+    fn select<G: Playable, R: Rng>(
+        &self,
+        nodes: &mut Vec<Node<G::Action>>,
+        state: &mut G,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        let mut node = 0;
+        let mut path = vec![0];
+        while let Some(player) = state.to_move() {
+            let legal = state.legal_actions();
+            if legal.is_empty() {
+                break;
+            }
+            let available = nodes[node]
+                .children
+                .iter()
+                .copied()
+                .filter(|c| legal.iter().any(|a| nodes[*c].action.as_ref() == Some(a)))
+                .collect::<Vec<_>>();
+            for c in &available {
+                nodes[*c].availability += 1;
+            }
+
+            let untried = legal
+                .iter()
+                .filter(|a| {
+                    !available
+                        .iter()
+                        .any(|c| nodes[*c].action.as_ref() == Some(*a))
+                })
+                .collect::<Vec<_>>();
+            if !untried.is_empty() {
+                let action = untried[rng.gen_range(0..untried.len())].clone();
+                state.apply(&action);
+                let child = nodes.len();
+                nodes.push(Node::new(Some(action), player));
+                nodes[node].children.push(child);
+                path.push(child);
+                break;
+            }
+
+            let ucb = |c: &usize| {
+                let n = &nodes[*c];
+                let visits = n.visits.max(1) as f64;
+                n.reward / visits
+                    + self.exploration * ((n.availability as f64).ln() / visits).sqrt()
+            };
+            let best = available
+                .iter()
+                .copied()
+                .max_by(|a, b| ucb(a).total_cmp(&ucb(b)))
+                .unwrap();
+            state.apply(nodes[best].action.as_ref().unwrap());
+            node = best;
+            path.push(best);
+        }
+        path
+    }
+
+    /// Plays random actions until the game is over or the playout is too long, and returns the
+    /// rewards
+    fn playout<G: Playable, R: Rng>(&self, state: &mut G, rng: &mut R) -> Vec<f64> {
+        for _ in 0..self.max_depth {
+            if state.to_move().is_none() {
+                break;
+            }
+            let legal = state.legal_actions();
+            if legal.is_empty() {
+                break;
+            }
+            state.apply(&legal[rng.gen_range(0..legal.len())]);
+        }
+        state.rewards()
+    }
+}
+
+/// Deals the unseen cards of a game at random, as the seat might imagine them. Returns `None` if
+/// no deal agrees with the constraints, so the search never sees the cards the seat cannot.
+pub fn determinized<R, T>(
+    game: &Game<R>,
+    seat: usize,
+    constraints: &[Constraint],
+    rng: &mut T,
+) -> Option<Game<R>>
+where
+    R: Rules<Card = Card> + Clone,
+    R::Hand: Clone,
+    T: Rng,
+{
+    let info = game.info_set(Some(seat), Card::all_cards());
+    let mut sampler = Sampler::new(&info);
+    for constraint in constraints {
+        let _ = sampler.constrain(*constraint);
+    }
+    let deal = sampler.sample(rng).ok()?;
+    let mut game = game.clone();
+    deal.apply(&mut game, &info).ok()?;
+    Some(game)
+}
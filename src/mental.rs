@@ -0,0 +1,219 @@
+//! Mental poker, which deals cards between players who do not trust each other or a dealer.
+//!
+//! This is the protocol of Shamir, Rivest and Adleman. Cards are encrypted by raising them to a
+//! secret power modulo a shared prime, and since `(m^a)^b = (m^b)^a` the players can add and
+//! remove their encryption in any order.
+//!
+//! 1. The deck is encoded as numbers with `Group::encode`.
+//! 2. Each player in turn encrypts every card with one key and shuffles them with `shuffle`.
+//! 3. Each player in turn swaps that key for a different key on each card with `lock`.
+//! 4. To deal the card at a position to a player, every other player removes their lock from it
+//!    with `unlock` and the player it is dealt to reads it with `open`.
+//! 5. After the game everyone shows their keys and the deal is checked with `verify`.
+//!
+//! No player knows the order of the deck unless every player shares their shuffle, and a card
+//! can only be read with the help of every other player. Cards are encoded as squares modulo a
+//! safe prime, so the encryption does not leak whether a card is a quadratic residue.
+//!
+//! # Example
+//!
+//! ```
+//! use deckofcards::mental::{self, Group, Player};
+//! use deckofcards::{Deck, Decky};
+//! use rand::rngs::StdRng;
+//! use rand::SeedableRng;
+//!
+//! let group = Group::test();
+//! let mut rng = StdRng::seed_from_u64(1);
+//! let mut alice = Player::new(&group, &mut rng);
+//! let mut bob = Player::new(&group, &mut rng);
+//!
+//! let deck = group.encode(&Deck::new());
+//! let deck = alice.shuffle(&deck, &mut rng);
+//! let deck = bob.shuffle(&deck, &mut rng);
+//! let deck = alice.lock(&deck, &mut rng).unwrap();
+//! let deck = bob.lock(&deck, &mut rng).unwrap();
+//!
+//! // Bob deals the top card to Alice
+//! let card = bob.unlock(0, &deck[0]).unwrap();
+//! let card = alice.open(0, &card).unwrap();
+//!
+//! let cards = mental::verify(&group, &deck, &[alice.keys(), bob.keys()]).unwrap();
+//! assert_eq!(cards[0], card);
+//! ```
+
+use num_bigint::{BigUint, RandBigInt};
+use rand::seq::SliceRandom;
+use rand::{CryptoRng, Rng};
+
+use super::*;
+
+/// The prime of the 2048-bit group of RFC 3526, which is a safe prime
+const RFC3526_2048: &str = "\
+FFFFFFFFFFFFFFFFC90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B139B22514A08798E3404DD\
+EF9519B3CD3A431B302B0A6DF25F14374FE1356D6D51C245E485B576625E7EC6F44C42E9A637ED6B0BFF5CB6F406B7ED\
+EE386BFB5A899FA5AE9F24117C4B1FE649286651ECE45B3DC2007CB8A163BF0598DA48361C55D39A69163FA8FD24CF5F\
+83655D23DCA3AD961C62F356208552BB9ED529077096966D670C354E4ABC9804F1746C08CA18217C32905E462E36CE3B\
+E39E772C180E86039B2783A2EC07A28FB5C55DF06F4C52C9DE2BCBF6955817183995497CEA956AE515D2261898FA0510\
+15728E5A8AACAA68FFFFFFFFFFFFFFFF";
+
+/// A 256-bit safe prime, which is quick but too small to be safe
+const TEST_256: &str = "9BE54368FDAEB7E5D45D1EF3F6A1EAF9642CB55F167E01B5378E341AFE8737AB";
+
+/// The numbers every player works with, a safe prime `p = 2q + 1`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Group {
+    p: BigUint,
+    q: BigUint,
+}
+
+impl Group {
+    /// Creates a group from a safe prime, which must be larger than 2809 to encode every card.
+    /// The prime is not checked.
+    pub fn new(p: BigUint) -> Result<Group, &'static str> {
+        if p <= BigUint::from(2809u32) || !p.bit(0) {
+            return Err("Prime is too small or even");
+        }
+        let q = (&p - 1u32) >> 1;
+        Ok(Group { p, q })
+    }
+
+    /// Returns the 2048-bit group of RFC 3526
+    pub fn standard() -> Group {
+        Group::new(BigUint::parse_bytes(RFC3526_2048.as_bytes(), 16).unwrap()).unwrap()
+    }
+
+    /// Returns a 256-bit group, which is fast enough for tests but offers no real security
+    pub fn test() -> Group {
+        Group::new(BigUint::parse_bytes(TEST_256.as_bytes(), 16).unwrap()).unwrap()
+    }
+
+    /// Encodes the cards of the deck in order, as the square of two more than each card's ordinal
+    pub fn encode(&self, deck: &Deck<Card>) -> Vec<BigUint> {
+        deck.cards()
+            .iter()
+            .map(|c| BigUint::from(c.ordinal() as u32 + 2).pow(2) % &self.p)
+            .collect()
+    }
+
+    /// Decodes a number to the card it encodes
+    pub fn decode(&self, value: &BigUint) -> Result<Card, &'static str> {
+        let root = value.sqrt();
+        match u32::try_from(&root) {
+            Ok(root) if &(BigUint::from(root).pow(2)) == value && root >= 2 => {
+                Card::from_ordinal(root as usize - 2).map_err(|_| "Not a card")
+            }
+            _ => Err("Not a card"),
+        }
+    }
+
+    /// Creates a key pair, an exponent and its inverse in the group of squares
+    fn key<R: Rng + CryptoRng + ?Sized>(&self, rng: &mut R) -> (BigUint, BigUint) {
+        loop {
+            let e = rng.gen_biguint_range(&BigUint::from(2u32), &self.q);
+            if let Some(d) = e.modinv(&self.q) {
+                return (e, d);
+            }
+        }
+    }
+}
+
+/// One player's side of the protocol, which holds their secret keys
+pub struct Player {
+    group: Group,
+    shuffle_key: (BigUint, BigUint),
+    card_keys: Vec<(BigUint, BigUint)>,
+}
+
+impl Player {
+    /// Creates a player with a new shuffle key
+    pub fn new<R: Rng + CryptoRng + ?Sized>(group: &Group, rng: &mut R) -> Player {
+        Player {
+            group: group.clone(),
+            shuffle_key: group.key(rng),
+            card_keys: Vec::new(),
+        }
+    }
+
+    /// Encrypts every card of the deck with the player's shuffle key and shuffles them
+    pub fn shuffle<R: Rng + CryptoRng + ?Sized>(
+        &mut self,
+        deck: &[BigUint],
+        rng: &mut R,
+    ) -> Vec<BigUint> {
+        let mut deck = deck
+            .iter()
+            .map(|c| c.modpow(&self.shuffle_key.0, &self.group.p))
+            .collect::<Vec<_>>();
+        deck.shuffle(rng);
+        deck
+    }
+
+    /// Removes the player's shuffle key from every card of the deck and encrypts each card with a
+    /// key of its own, once every player has shuffled
+    pub fn lock<R: Rng + CryptoRng + ?Sized>(
+        &mut self,
+        deck: &[BigUint],
+        rng: &mut R,
+    ) -> Result<Vec<BigUint>, &'static str> {
+        if !self.card_keys.is_empty() {
+            return Err("Deck is already locked");
+        }
+        self.card_keys = deck.iter().map(|_| self.group.key(rng)).collect();
+        Ok(deck
+            .iter()
+            .zip(&self.card_keys)
+            .map(|(c, (e, _))| {
+                let swap = (&self.shuffle_key.1 * e) % &self.group.q;
+                c.modpow(&swap, &self.group.p)
+            })
+            .collect())
+    }
+
+    /// Removes the player's lock from the card at the position in the deck, so it can be passed
+    /// on towards the player it is dealt to
+    pub fn unlock(&self, position: usize, card: &BigUint) -> Result<BigUint, &'static str> {
+        let (_, d) = self.card_keys.get(position).ok_or("No such card")?;
+        Ok(card.modpow(d, &self.group.p))
+    }
+
+    /// Reads a card dealt to the player once every other player has unlocked it
+    pub fn open(&self, position: usize, card: &BigUint) -> Result<Card, &'static str> {
+        self.group.decode(&self.unlock(position, card)?)
+    }
+
+    /// Returns the keys which unlock each card, to show the other players after the game
+    pub fn keys(&self) -> Vec<BigUint> {
+        self.card_keys.iter().map(|(_, d)| d.clone()).collect()
+    }
+}
+
+/// Unlocks every card of a locked deck with the keys each player showed after the game, and checks
+/// it holds each of the 52 cards once. This catches a player who changed cards while shuffling or
+/// locking, or who shows keys that do not unlock the deck. It does not see the cards passed on
+/// during the game, so it cannot catch a player who unlocked a card with some other key.
+pub fn verify(
+    group: &Group,
+    deck: &[BigUint],
+    keys: &[Vec<BigUint>],
+) -> Result<Vec<Card>, &'static str> {
+    if deck.len() != 52 {
+        return Err("Deck does not hold 52 cards");
+    }
+    if keys.iter().any(|k| k.len() != deck.len()) {
+        return Err("Keys do not match the deck");
+    }
+    let mut seen = [false; 52];
+    let mut cards = Vec::with_capacity(deck.len());
+    for (position, card) in deck.iter().enumerate() {
+        let value = keys
+            .iter()
+            .fold(card.clone(), |c, k| c.modpow(&k[position], &group.p));
+        let card = group.decode(&value)?;
+        if core::mem::replace(&mut seen[card.ordinal()], true) {
+            return Err("Card appears twice");
+        }
+        cards.push(card);
+    }
+    Ok(cards)
+}
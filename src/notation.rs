@@ -0,0 +1,210 @@
+//! A card parser which accepts the many ways people write cards.
+//!
+//! `Card::from_str` only accepts the exact two character form such as "AS". The parser here also
+//! accepts the pretty form "A♠", English names such as "Ace of Spades", the Unicode playing card
+//! glyphs such as "🂡" and, unless it is strict, lowercase, "10" for Ten and the suit before the
+//! rank. Errors say where in the string the problem is.
+
+use core::fmt;
+
+use super::*;
+
+/// How forgiving the parser is
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum Strictness {
+    /// Only accepts the forms this crate writes, i.e. "AS", "A♠", "Ace of Spades" and the glyphs
+    Strict,
+    /// Also accepts any case, "10" for Ten, "1" for Ace, the suit before the rank, singular
+    /// suit names and surrounding whitespace
+    #[default]
+    Lenient,
+}
+
+/// The reason a card could not be parsed and where in the string the problem starts
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte offset into the string
+    pub position: usize,
+    /// Why the string could not be parsed
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.reason, self.position)
+    }
+}
+
+/// Parses a card written in any of the supported notations
+///
+/// # Example
+///
+/// ```
+/// use deckofcards::notation::{parse_card, Strictness};
+/// use deckofcards::{Card, Rank, Suit};
+///
+/// let ten_of_hearts = Card::new(Rank::Ten, Suit::Hearts);
+/// assert_eq!(parse_card("10h", Strictness::Lenient), Ok(ten_of_hearts));
+/// assert_eq!(parse_card("T♥", Strictness::Strict), Ok(ten_of_hearts));
+/// assert_eq!(parse_card("Ten of Hearts", Strictness::Strict), Ok(ten_of_hearts));
+/// assert_eq!(parse_card("🂺", Strictness::Strict), Ok(ten_of_hearts));
+/// assert_eq!(parse_card("10h", Strictness::Strict).unwrap_err().position, 0);
+/// ```
+pub fn parse_card(s: &str, strictness: Strictness) -> Result<Card, ParseError> {
+    let lenient = strictness == Strictness::Lenient;
+    let (offset, s) = if lenient {
+        let trimmed = s.trim_start();
+        (s.len() - trimmed.len(), trimmed.trim_end())
+    } else {
+        (0, s)
+    };
+    let error = |position: usize, reason| ParseError {
+        position: offset + position,
+        reason,
+    };
+
+    if s.is_empty() {
+        return Err(error(0, "Empty string"));
+    }
+
+    let mut chars = s.chars();
+    if let (Some(ch), None) = (chars.next(), chars.next()) {
+        if let Ok(card) = Card::from_unicode_glyph(ch) {
+            return Ok(card);
+        }
+    }
+
+    if s.contains(' ') {
+        return parse_name(s, lenient).map_err(|(p, reason)| error(p, reason));
+    }
+    parse_short(s, lenient).map_err(|(p, reason)| error(p, reason))
+}
+
+/// Parses a card in the two character form with either a letter or symbol for the suit
+fn parse_short(s: &str, lenient: bool) -> Result<Card, (usize, &'static str)> {
+    let first = s.chars().next().unwrap_or_default();
+    let starts_with_suit = lenient && parse_suit_char(first, lenient).is_some();
+    let starts_with_rank = parse_rank_token(s, lenient).is_some();
+    if starts_with_suit && !starts_with_rank {
+        // Suit then rank, e.g. "SA" or "♠10"
+        let rest = &s[first.len_utf8()..];
+        let suit = parse_suit_char(first, lenient).unwrap();
+        let (rank, len) =
+            parse_rank_token(rest, lenient).ok_or((first.len_utf8(), "Invalid rank"))?;
+        expect_end(rest, len).map_err(|p| (first.len_utf8() + p, "Unexpected characters"))?;
+        return Ok(Card::new(rank, suit));
+    }
+
+    let (rank, len) = parse_rank_token(s, lenient).ok_or((0, "Invalid rank"))?;
+    let rest = &s[len..];
+    let suit_char = rest.chars().next().ok_or((len, "Missing suit"))?;
+    let suit = parse_suit_char(suit_char, lenient).ok_or((len, "Invalid suit"))?;
+    let end = len + suit_char.len_utf8();
+    expect_end(s, end).map_err(|p| (p, "Unexpected characters"))?;
+    Ok(Card::new(rank, suit))
+}
+
+/// Parses an English name such as "Ace of Spades"
+fn parse_name(s: &str, lenient: bool) -> Result<Card, (usize, &'static str)> {
+    let words = words(s);
+    let word = |i: usize| words.get(i).copied();
+    let (rank_at, rank_word) = word(0).ok_or((0, "Invalid rank"))?;
+    let rank = Rank::iterator()
+        .find(|r| matches_word(r.to_str(), rank_word, lenient))
+        .copied()
+        .or_else(|| {
+            // A short rank such as "10 of hearts" is fine when lenient
+            lenient
+                .then(|| parse_rank_token(rank_word, lenient))
+                .flatten()
+                .filter(|(_, len)| *len == rank_word.len())
+                .map(|(rank, _)| rank)
+        })
+        .ok_or((rank_at, "Invalid rank"))?;
+    let (of_at, of) = word(1).ok_or((s.len(), "Expected \"of\""))?;
+    if !matches_word("of", of, lenient) {
+        return Err((of_at, "Expected \"of\""));
+    }
+    let (suit_at, suit_word) = word(2).ok_or((s.len(), "Missing suit"))?;
+    let suit = Suit::iterator()
+        .find(|suit| {
+            let name = suit.to_str();
+            matches_word(name, suit_word, lenient)
+                || (lenient && matches_word(&name[..name.len() - 1], suit_word, lenient))
+        })
+        .copied()
+        .ok_or((suit_at, "Invalid suit"))?;
+    if let Some((extra_at, _)) = word(3) {
+        return Err((extra_at, "Unexpected characters"));
+    }
+    Ok(Card::new(rank, suit))
+}
+
+/// Parses a rank from the start of the string, returning it and the number of bytes used
+fn parse_rank_token(s: &str, lenient: bool) -> Option<(Rank, usize)> {
+    if lenient && s.starts_with("10") {
+        return Some((Rank::Ten, 2));
+    }
+    let ch = s.chars().next()?;
+    let ch = if lenient { ch.to_ascii_uppercase() } else { ch };
+    if !lenient && ch == '1' {
+        return None;
+    }
+    Rank::from_char(ch).ok().map(|rank| (rank, 1))
+}
+
+/// Parses a suit from a letter or a Unicode suit symbol
+fn parse_suit_char(ch: char, lenient: bool) -> Option<Suit> {
+    let letter = if lenient { ch.to_ascii_uppercase() } else { ch };
+    Suit::from_char(letter)
+        .or_else(|_| Suit::from_unicode(ch))
+        .or_else(|_| {
+            // The outlined symbols are also commonly used
+            let ch = match ch {
+                '♤' if lenient => '♠',
+                '♡' if lenient => '♥',
+                '♢' if lenient => '♦',
+                '♧' if lenient => '♣',
+                _ => ch,
+            };
+            Suit::from_unicode(ch)
+        })
+        .ok()
+}
+
+/// Splits the string into words and their byte offsets
+fn words(s: &str) -> Vec<(usize, &str)> {
+    let mut result = Vec::new();
+    let mut start = None;
+    for (i, ch) in s.char_indices() {
+        match (ch.is_whitespace(), start) {
+            (true, Some(from)) => {
+                result.push((from, &s[from..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(from) = start {
+        result.push((from, &s[from..]));
+    }
+    result
+}
+
+fn matches_word(expected: &str, word: &str, lenient: bool) -> bool {
+    if lenient {
+        expected.eq_ignore_ascii_case(word)
+    } else {
+        expected == word
+    }
+}
+
+/// Checks there is nothing after `end`, otherwise returns where the extra characters start
+fn expect_end(s: &str, end: usize) -> Result<(), usize> {
+    if s.len() > end {
+        Err(end)
+    } else {
+        Ok(())
+    }
+}
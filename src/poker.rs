@@ -0,0 +1,170 @@
+//! Poker hand evaluation.
+//!
+//! A hand of five to seven cards is valued by its best five cards. Values compare by category and
+//! then by the ranks which break ties, so any two values can be compared to find the winner. Aces
+//! are high except in the A-2-3-4-5 straight.
+
+use core::cmp::Ordering;
+use core::fmt;
+
+use super::*;
+
+/// The category of a five card poker hand, from lowest to highest
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl Category {
+    /// Returns the English name of the category, e.g. "Full House"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::HighCard => "High Card",
+            Category::OnePair => "One Pair",
+            Category::TwoPair => "Two Pair",
+            Category::ThreeOfAKind => "Three of a Kind",
+            Category::Straight => "Straight",
+            Category::Flush => "Flush",
+            Category::FullHouse => "Full House",
+            Category::FourOfAKind => "Four of a Kind",
+            Category::StraightFlush => "Straight Flush",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The value of the best five cards of a hand. Values are equal when they tie, even if their
+/// cards differ in suit.
+#[derive(Clone, Debug)]
+pub struct Value {
+    /// The category of the hand, e.g. Flush
+    pub category: Category,
+    /// The ranks that break ties within the category, most significant first
+    pub ranks: Vec<Rank>,
+    /// The five cards making the hand, grouped and ordered like `ranks`
+    pub cards: Vec<Card>,
+}
+
+impl Ord for Value {
+    fn cmp(&self, other: &Value) -> Ordering {
+        self.category
+            .cmp(&other.category)
+            .then_with(|| self.ranks.cmp(&other.ranks))
+    }
+}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Value) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+/// Finds the value of the best five cards out of five to seven cards
+///
+/// # Example
+///
+/// ```
+/// use deckofcards::poker::{evaluate, Category};
+/// use deckofcards::{hand, Cards};
+///
+/// let royal_flush = hand!("AS", "KS", "QS", "JS", "TS", "2D", "2C");
+/// let four_aces = hand!("AD", "AC", "AH", "AS", "KD");
+/// let value = evaluate(royal_flush.cards()).unwrap();
+/// assert_eq!(value.category, Category::StraightFlush);
+/// assert!(value > evaluate(four_aces.cards()).unwrap());
+/// ```
+pub fn evaluate(cards: &[Card]) -> Result<Value, &'static str> {
+    if cards.len() < 5 || cards.len() > 7 {
+        return Err("A poker hand needs five to seven cards");
+    }
+    for (i, card) in cards.iter().enumerate() {
+        if cards[i + 1..].contains(card) {
+            return Err("A poker hand cannot contain the same card twice");
+        }
+    }
+
+    // Each combination of five cards is a bit mask with five bits set
+    let best = (0u32..1 << cards.len())
+        .filter(|mask| mask.count_ones() == 5)
+        .map(|mask| {
+            let five = cards
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, card)| *card)
+                .collect();
+            evaluate_five(five)
+        })
+        .max();
+    Ok(best.unwrap())
+}
+
+/// Values exactly five distinct cards
+fn evaluate_five(mut cards: Vec<Card>) -> Value {
+    cards.sort_by(|a, b| a.cmp_desc_rank_then_suit(b));
+
+    // Group the cards by rank, larger groups first and then higher ranks
+    let mut groups: Vec<Vec<Card>> = Vec::new();
+    for card in &cards {
+        match groups.last_mut() {
+            Some(group) if group[0].rank == card.rank => group.push(*card),
+            _ => groups.push(vec![*card]),
+        }
+    }
+    groups.sort_by(|a, b| b.len().cmp(&a.len()).then(b[0].rank.cmp(&a[0].rank)));
+
+    let flush = cards.iter().all(|c| c.suit == cards[0].suit);
+    let high = cards[0].rank.ordinal();
+    let straight = groups.len() == 5
+        && (high - cards[4].rank.ordinal() == 4
+            || (cards[0].rank == Rank::Ace && cards[1].rank == Rank::Five));
+
+    let mut ranks = groups.iter().map(|g| g[0].rank).collect::<Vec<_>>();
+    let mut cards = groups.concat();
+    if straight && ranks[0] == Rank::Ace && ranks[1] == Rank::Five {
+        // The ace plays low in the wheel
+        ranks.rotate_left(1);
+        cards.rotate_left(1);
+    }
+
+    let category = match (groups[0].len(), groups[1].len()) {
+        _ if straight && flush => Category::StraightFlush,
+        (4, _) => Category::FourOfAKind,
+        (3, 2) => Category::FullHouse,
+        _ if flush => Category::Flush,
+        _ if straight => Category::Straight,
+        (3, _) => Category::ThreeOfAKind,
+        (2, 2) => Category::TwoPair,
+        (2, _) => Category::OnePair,
+        _ => Category::HighCard,
+    };
+    if straight {
+        ranks.truncate(1);
+    }
+    Value {
+        category,
+        ranks,
+        cards,
+    }
+}
@@ -0,0 +1,501 @@
+//! A parser for PokerStars and Full Tilt text hand histories.
+//!
+//! A file of hand histories is read with `parse()` which returns a `PokerHand` for each hand in
+//! it. Lines which are not understood, such as chat or players joining the table, are skipped.
+//! Amounts are kept in hundredths of the currency or chip unit, so "$1.25" is 125 and a
+//! tournament stack of "1500" is 150000.
+
+use core::fmt;
+use core::str::FromStr;
+
+use super::*;
+
+/// The site a hand was played on
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Site {
+    PokerStars,
+    FullTilt,
+}
+
+/// A betting round of the hand
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+    Showdown,
+}
+
+/// A player sitting at the table
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Seat {
+    /// The seat number, starting from 1
+    pub number: usize,
+    /// The player's name
+    pub name: String,
+    /// The player's stack at the start of the hand
+    pub stack: u64,
+}
+
+/// What a player did
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ActionKind {
+    /// Posted the small blind
+    SmallBlind(u64),
+    /// Posted the big blind
+    BigBlind(u64),
+    /// Posted an ante
+    Ante(u64),
+    /// Posted any other blind, e.g. when joining the table
+    Post(u64),
+    Fold,
+    Check,
+    Call(u64),
+    Bet(u64),
+    /// Raised by the first amount to a total of the second amount
+    Raise(u64, u64),
+    /// An uncalled bet was returned to the player
+    Return(u64),
+    /// Showed the cards
+    Show(Vec<Card>),
+    /// Threw away the cards without showing them
+    Muck,
+}
+
+/// An action taken by a player
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Action {
+    /// The betting round the action happened in
+    pub street: Street,
+    /// The name of the player
+    pub player: String,
+    /// What the player did
+    pub kind: ActionKind,
+    /// Whether the action put the player all in
+    pub all_in: bool,
+}
+
+/// An amount won by a player
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Collected {
+    /// The name of the player
+    pub player: String,
+    /// The amount won from the pot or a side pot
+    pub amount: u64,
+}
+
+/// A `PokerHand` is everything recorded about one hand
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PokerHand {
+    /// The site the hand was played on
+    pub site: Site,
+    /// The hand number given by the site
+    pub id: String,
+    /// A description of the game and stakes, e.g. "Hold'em No Limit ($0.01/$0.02 USD)"
+    pub game: String,
+    /// The table name
+    pub table: String,
+    /// The number of the seat with the dealer button
+    pub button: Option<usize>,
+    /// The players at the table
+    pub seats: Vec<Seat>,
+    /// The hole cards dealt to or shown by each player
+    pub hole_cards: Vec<(String, Vec<Card>)>,
+    /// The community cards
+    pub board: Vec<Card>,
+    /// Everything the players did, in order
+    pub actions: Vec<Action>,
+    /// The amounts won by each player
+    pub collected: Vec<Collected>,
+    /// The total pot from the summary
+    pub total_pot: Option<u64>,
+    /// The rake from the summary
+    pub rake: Option<u64>,
+}
+
+impl PokerHand {
+    /// Returns the hole cards known for the player
+    pub fn hole_cards_of(&self, player: &str) -> Option<&[Card]> {
+        self.hole_cards
+            .iter()
+            .find(|(name, _)| name == player)
+            .map(|(_, cards)| cards.as_slice())
+    }
+}
+
+/// The reason a hand history could not be parsed and the line it happened on
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line number, starting from 1
+    pub line: usize,
+    /// Why the line could not be parsed
+    pub reason: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+impl FromStr for PokerHand {
+    type Err = ParseError;
+
+    /// Parses the text of a single hand
+    fn from_str(s: &str) -> Result<PokerHand, ParseError> {
+        let mut hands = parse(s)?;
+        match hands.len() {
+            1 => Ok(hands.remove(0)),
+            _ => Err(ParseError {
+                line: 1,
+                reason: "Expected exactly one hand",
+            }),
+        }
+    }
+}
+
+/// Parses every hand in the text
+pub fn parse(text: &str) -> Result<Vec<PokerHand>, ParseError> {
+    let mut hands = Vec::new();
+    let mut parser: Option<Parser> = None;
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim_start_matches('\u{feff}').trim();
+        let error = |reason| ParseError {
+            line: i + 1,
+            reason,
+        };
+        if let Some(hand) = parse_header(line).map_err(error)? {
+            if let Some(parser) = parser.take() {
+                hands.push(parser.hand);
+            }
+            parser = Some(Parser {
+                hand,
+                street: Street::Preflop,
+                summary: false,
+            });
+        } else if let Some(parser) = parser.as_mut() {
+            parser.parse_line(line).map_err(error)?;
+        } else if !line.is_empty() {
+            return Err(error("Expected a hand header"));
+        }
+    }
+    if let Some(parser) = parser {
+        hands.push(parser.hand);
+    }
+    Ok(hands)
+}
+
+struct Parser {
+    hand: PokerHand,
+    street: Street,
+    summary: bool,
+}
+
+impl Parser {
+    fn parse_line(&mut self, line: &str) -> Result<(), &'static str> {
+        if let Some(rest) = line.strip_prefix("*** ") {
+            return self.parse_street(rest);
+        }
+        if self.summary {
+            return self.parse_summary(line);
+        }
+        if let Some(rest) = line.strip_prefix("Seat ") {
+            if let Some((number, rest)) = rest.split_once(": ") {
+                if let Ok(number) = number.parse() {
+                    return self.parse_seat(number, rest);
+                }
+            }
+        }
+        if let Some(rest) = line.strip_prefix("Table '") {
+            if let Some((table, rest)) = rest.split_once('\'') {
+                self.hand.table = table.to_string();
+                self.hand.button =
+                    between(rest, "Seat #", " is the button").and_then(|b| b.parse().ok());
+            }
+            return Ok(());
+        }
+        if let Some(button) = line.strip_prefix("The button is in seat #") {
+            self.hand.button = button.parse().ok();
+            return Ok(());
+        }
+        if let Some(rest) = line.strip_prefix("Dealt to ") {
+            if let Some((player, cards)) = self.split_player(rest) {
+                if let Some(cards) = between(cards, "[", "]") {
+                    let cards = parse_cards(cards)?;
+                    self.add_hole_cards(&player, cards);
+                }
+            }
+            return Ok(());
+        }
+        if let Some(rest) = line.strip_prefix("Uncalled bet ") {
+            let (amount, player) = rest
+                .split_once(" returned to ")
+                .ok_or("Invalid uncalled bet")?;
+            let amount = amount.trim_start_matches("of ");
+            let amount = amount.trim_start_matches('(').trim_end_matches(')');
+            self.push_action(player, ActionKind::Return(parse_amount(amount)?), false);
+            return Ok(());
+        }
+        if let Some((player, rest)) = self.split_player(line) {
+            return self.parse_action(&player, rest);
+        }
+        Ok(())
+    }
+
+    fn parse_street(&mut self, rest: &str) -> Result<(), &'static str> {
+        let (name, cards) = rest.split_once(" ***").ok_or("Invalid street")?;
+        self.street = match name {
+            "HOLE CARDS" | "POCKET CARDS" => Street::Preflop,
+            "FLOP" => Street::Flop,
+            "TURN" => Street::Turn,
+            "RIVER" => Street::River,
+            "SHOW DOWN" | "SHOWDOWN" => Street::Showdown,
+            "SUMMARY" => {
+                self.summary = true;
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+        // The new cards are in the last pair of brackets
+        if let Some(start) = cards.rfind('[') {
+            let cards = between(&cards[start..], "[", "]").ok_or("Invalid board")?;
+            self.hand.board.extend(parse_cards(cards)?);
+        }
+        Ok(())
+    }
+
+    fn parse_summary(&mut self, line: &str) -> Result<(), &'static str> {
+        if let Some(rest) = line.strip_prefix("Total pot ") {
+            let mut parts = rest.split(" | ");
+            let pot = parts.next().unwrap_or_default();
+            // Side pots follow the total, e.g. "$10 Main pot $6. Side pot $4."
+            let pot = pot.split_whitespace().next().unwrap_or_default();
+            self.hand.total_pot = Some(parse_amount(pot)?);
+            for part in parts {
+                if let Some(rake) = part.strip_prefix("Rake ") {
+                    self.hand.rake = Some(parse_amount(rake.trim())?);
+                }
+            }
+        } else if let Some(rest) = line
+            .strip_prefix("Board: ")
+            .or_else(|| line.strip_prefix("Board "))
+        {
+            let cards = between(rest, "[", "]").ok_or("Invalid board")?;
+            self.hand.board = parse_cards(cards)?;
+        }
+        Ok(())
+    }
+
+    fn parse_seat(&mut self, number: usize, rest: &str) -> Result<(), &'static str> {
+        // The stack is in the last parentheses starting with an amount, anything after it such
+        // as "is sitting out" is ignored
+        let start = rest
+            .match_indices(" (")
+            .map(|(i, _)| i)
+            .filter(|i| {
+                rest[i + 2..].starts_with(|c: char| c.is_ascii_digit() || "$€£".contains(c))
+            })
+            .last()
+            .ok_or("Invalid seat")?;
+        let stack = rest[start + 2..]
+            .split([' ', ')', ','])
+            .next()
+            .unwrap_or_default();
+        self.hand.seats.push(Seat {
+            number,
+            name: rest[..start].to_string(),
+            stack: parse_amount(stack)?,
+        });
+        Ok(())
+    }
+
+    fn parse_action(&mut self, player: &str, rest: &str) -> Result<(), &'static str> {
+        let rest = rest.trim_start_matches(": ").trim_start();
+        let all_in = rest.ends_with("and is all-in") || rest.ends_with("and is all in");
+        let rest = rest
+            .trim_end_matches("and is all-in")
+            .trim_end_matches("and is all in")
+            .trim_end_matches([' ', ',']);
+        let words = rest.split_whitespace().collect::<Vec<_>>();
+        let last_amount = || parse_amount(words.last().copied().unwrap_or_default());
+        let kind = match words.as_slice() {
+            ["folds", ..] => ActionKind::Fold,
+            ["checks", ..] => ActionKind::Check,
+            ["calls", amount] => ActionKind::Call(parse_amount(amount)?),
+            ["bets", amount] => ActionKind::Bet(parse_amount(amount)?),
+            ["raises", by, "to", to] => ActionKind::Raise(parse_amount(by)?, parse_amount(to)?),
+            ["raises", "to", to] => {
+                // Full Tilt gives only the total, the raise is worked out from the largest bet
+                let to = parse_amount(to)?;
+                ActionKind::Raise(to.saturating_sub(self.largest_bet()), to)
+            }
+            ["posts", "small", "blind", ..] | ["posts", "the", "small", "blind", ..] => {
+                ActionKind::SmallBlind(last_amount()?)
+            }
+            ["posts", "big", "blind", ..] | ["posts", "the", "big", "blind", ..] => {
+                ActionKind::BigBlind(last_amount()?)
+            }
+            ["posts", "the", "ante", ..] | ["antes", ..] => ActionKind::Ante(last_amount()?),
+            ["posts", ..] => ActionKind::Post(last_amount()?),
+            ["shows", ..] => {
+                let cards = between(rest, "[", "]").ok_or("Invalid cards")?;
+                let cards = parse_cards(cards)?;
+                self.add_hole_cards(player, cards.clone());
+                ActionKind::Show(cards)
+            }
+            ["mucks", ..] => ActionKind::Muck,
+            ["collected", amount, ..] => {
+                self.collect(player, parse_amount(amount)?);
+                return Ok(());
+            }
+            ["wins", ..] => {
+                let amount = between(rest, "(", ")").ok_or("Invalid amount")?;
+                self.collect(player, parse_amount(amount)?);
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+        self.push_action(player, kind, all_in);
+        Ok(())
+    }
+
+    /// Splits the name of a seated player from the start of the line, preferring the longest
+    /// name since names may contain spaces.
+    fn split_player<'a>(&self, line: &'a str) -> Option<(String, &'a str)> {
+        self.hand
+            .seats
+            .iter()
+            .filter(|s| {
+                line.strip_prefix(s.name.as_str())
+                    .is_some_and(|rest| rest.starts_with(' ') || rest.starts_with(':'))
+            })
+            .max_by_key(|s| s.name.len())
+            .map(|s| (s.name.clone(), &line[s.name.len()..]))
+    }
+
+    fn push_action(&mut self, player: &str, kind: ActionKind, all_in: bool) {
+        self.hand.actions.push(Action {
+            street: self.street,
+            player: player.to_string(),
+            kind,
+            all_in,
+        });
+    }
+
+    fn add_hole_cards(&mut self, player: &str, cards: Vec<Card>) {
+        match self.hand.hole_cards.iter_mut().find(|(p, _)| p == player) {
+            Some((_, existing)) => *existing = cards,
+            None => self.hand.hole_cards.push((player.to_string(), cards)),
+        }
+    }
+
+    fn collect(&mut self, player: &str, amount: u64) {
+        self.hand.collected.push(Collected {
+            player: player.to_string(),
+            amount,
+        });
+    }
+
+    /// Returns the most any player has put in on this street
+    fn largest_bet(&self) -> u64 {
+        let mut totals: Vec<(&str, u64)> = Vec::new();
+        let street = self.hand.actions.iter().filter(|a| a.street == self.street);
+        for action in street {
+            let total = match totals.iter().position(|(p, _)| *p == action.player) {
+                Some(i) => totals[i].1,
+                None => 0,
+            };
+            let total = match action.kind {
+                ActionKind::SmallBlind(a) | ActionKind::BigBlind(a) | ActionKind::Post(a) => {
+                    total.saturating_add(a)
+                }
+                ActionKind::Call(a) | ActionKind::Bet(a) => total.saturating_add(a),
+                // A raise to an amount makes that the player's total for the street
+                ActionKind::Raise(_, to) => to,
+                _ => continue,
+            };
+            match totals.iter_mut().find(|(p, _)| *p == action.player) {
+                Some((_, t)) => *t = total,
+                None => totals.push((&action.player, total)),
+            }
+        }
+        totals.iter().map(|(_, t)| *t).max().unwrap_or_default()
+    }
+}
+
+/// Parses the first line of a hand, or returns `None` if the line does not start a hand
+fn parse_header(line: &str) -> Result<Option<PokerHand>, &'static str> {
+    let (site, rest) = if let Some(rest) = line.strip_prefix("PokerStars ") {
+        (Site::PokerStars, rest)
+    } else if let Some(rest) = line.strip_prefix("Full Tilt Poker ") {
+        (Site::FullTilt, rest)
+    } else {
+        return Ok(None);
+    };
+    let rest = rest.split_once('#').ok_or("Invalid header")?.1;
+    let (id, rest) = rest.split_once(':').ok_or("Invalid header")?;
+    let parts = rest.trim().split(" - ").collect::<Vec<_>>();
+    let (table, game) = match site {
+        Site::PokerStars => (String::new(), parts[0].to_string()),
+        Site::FullTilt => {
+            let table = parts[0].trim_start_matches("Table ");
+            let table = table.split(" (").next().unwrap_or(table);
+            let game = parts[1..parts.len().saturating_sub(2).max(1)].join(" - ");
+            (table.to_string(), game)
+        }
+    };
+    Ok(Some(PokerHand {
+        site,
+        id: id.trim().to_string(),
+        game,
+        table,
+        button: None,
+        seats: Vec::new(),
+        hole_cards: Vec::new(),
+        board: Vec::new(),
+        actions: Vec::new(),
+        collected: Vec::new(),
+        total_pot: None,
+        rake: None,
+    }))
+}
+
+/// Returns the text between the first `start` and the following `end`
+fn between<'a>(s: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let (_, rest) = s.split_once(start)?;
+    rest.split_once(end).map(|(inner, _)| inner)
+}
+
+/// Parses space separated cards such as "Ah Kd"
+fn parse_cards(s: &str) -> Result<Vec<Card>, &'static str> {
+    s.split_whitespace()
+        .map(|c| Card::from_str(&c.to_uppercase()))
+        .collect()
+}
+
+/// Parses an amount such as "$1.25" or "1,500" into hundredths
+fn parse_amount(s: &str) -> Result<u64, &'static str> {
+    let s = s
+        .trim_start_matches(['$', '€', '£'])
+        .trim_end_matches(" USD")
+        .replace(',', "");
+    let (whole, fraction) = s.split_once('.').unwrap_or((&s, ""));
+    if whole.is_empty() && fraction.is_empty() || fraction.len() > 2 {
+        return Err("Invalid amount");
+    }
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| "Invalid amount")?
+    };
+    let fraction: u64 = match fraction.len() {
+        0 => 0,
+        1 => fraction.parse::<u64>().map_err(|_| "Invalid amount")? * 10,
+        _ => fraction.parse().map_err(|_| "Invalid amount")?,
+    };
+    whole
+        .checked_mul(100)
+        .and_then(|w| w.checked_add(fraction))
+        .ok_or("Amount is too large")
+}
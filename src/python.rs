@@ -0,0 +1,424 @@
+//! Python bindings made with PyO3.
+//!
+//! The module is called `deckofcards` and has the classes `Card`, `Rank`, `Suit`, `Deck` and
+//! `Hand`. Cards compare with `Card::cmp_rank_then_suit`, every class can be pickled, and
+//! iterating over a deck gives its undealt cards in the order they would be dealt. Build it with
+//! `cargo rustc --lib --release --features python,pyo3/extension-module --crate-type cdylib`.
+//!
+//! ```text
+//! >>> from deckofcards import Deck
+//! >>> deck = Deck()
+//! >>> deck.shuffle(42)
+//! >>> hand = deck.deal(5)
+//! >>> sorted(hand)
+//! ```
+
+// The code generated by #[pymethods] for methods returning PyResult trips this lint
+#![allow(clippy::useless_conversion)]
+
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::{PyIndexError, PyValueError};
+use pyo3::prelude::*;
+
+use super::*;
+
+/// The rank of a card, see `Rank`
+#[pyclass(name = "Rank", module = "deckofcards", eq, eq_int)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum PyRank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl From<Rank> for PyRank {
+    fn from(rank: Rank) -> PyRank {
+        RANKS[rank.ordinal()]
+    }
+}
+
+impl From<PyRank> for Rank {
+    fn from(rank: PyRank) -> Rank {
+        Rank::ranks()[RANKS.iter().position(|r| *r == rank).unwrap()]
+    }
+}
+
+/// Pickles a variant of an enum class as `getattr(cls, name)`
+fn reduce_variant(
+    slf: &Bound<PyAny>,
+    name: &'static str,
+) -> PyResult<(PyObject, (PyObject, &'static str))> {
+    let getattr = slf.py().import_bound("builtins")?.getattr("getattr")?;
+    let cls = slf.get_type().into_any();
+    Ok((getattr.unbind(), (cls.unbind(), name)))
+}
+
+#[pymethods]
+impl PyRank {
+    fn __reduce__(slf: &Bound<PyRank>) -> PyResult<(PyObject, (PyObject, &'static str))> {
+        reduce_variant(slf.as_any(), Rank::from(*slf.borrow()).to_str())
+    }
+}
+
+/// Python ranks in the order of `Rank::ranks()`
+const RANKS: [PyRank; 13] = [
+    PyRank::Two,
+    PyRank::Three,
+    PyRank::Four,
+    PyRank::Five,
+    PyRank::Six,
+    PyRank::Seven,
+    PyRank::Eight,
+    PyRank::Nine,
+    PyRank::Ten,
+    PyRank::Jack,
+    PyRank::Queen,
+    PyRank::King,
+    PyRank::Ace,
+];
+
+/// The suit of a card, see `Suit`
+#[pyclass(name = "Suit", module = "deckofcards", eq, eq_int)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum PySuit {
+    Spades,
+    Hearts,
+    Diamonds,
+    Clubs,
+}
+
+impl From<Suit> for PySuit {
+    fn from(suit: Suit) -> PySuit {
+        match suit {
+            Suit::Spades => PySuit::Spades,
+            Suit::Hearts => PySuit::Hearts,
+            Suit::Diamonds => PySuit::Diamonds,
+            Suit::Clubs => PySuit::Clubs,
+        }
+    }
+}
+
+impl From<PySuit> for Suit {
+    fn from(suit: PySuit) -> Suit {
+        match suit {
+            PySuit::Spades => Suit::Spades,
+            PySuit::Hearts => Suit::Hearts,
+            PySuit::Diamonds => Suit::Diamonds,
+            PySuit::Clubs => Suit::Clubs,
+        }
+    }
+}
+
+#[pymethods]
+impl PySuit {
+    fn __reduce__(slf: &Bound<PySuit>) -> PyResult<(PyObject, (PyObject, &'static str))> {
+        reduce_variant(slf.as_any(), Suit::from(*slf.borrow()).to_str())
+    }
+}
+
+/// A playing card, see `Card`
+#[pyclass(name = "Card", module = "deckofcards", frozen)]
+#[derive(Copy, Clone)]
+pub struct PyCard {
+    pub card: Card,
+}
+
+fn to_card(s: &str) -> PyResult<Card> {
+    notation::parse_card(s, notation::Strictness::Lenient)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+fn to_cards(cards: Option<Vec<String>>) -> PyResult<Vec<Card>> {
+    cards
+        .unwrap_or_default()
+        .iter()
+        .map(|c| to_card(c))
+        .collect()
+}
+
+fn to_strings(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(|c| c.to_str()).collect()
+}
+
+fn to_py_cards(cards: &[Card]) -> Vec<PyCard> {
+    cards.iter().map(|card| PyCard { card: *card }).collect()
+}
+
+#[pymethods]
+impl PyCard {
+    /// Parses a card such as "AS", "10h" or "Ace of Spades"
+    #[new]
+    fn new(s: &str) -> PyResult<PyCard> {
+        Ok(PyCard { card: to_card(s)? })
+    }
+
+    /// Creates a card from its rank and suit
+    #[staticmethod]
+    fn from_rank_suit(rank: PyRank, suit: PySuit) -> PyCard {
+        PyCard {
+            card: Card::new(rank.into(), suit.into()),
+        }
+    }
+
+    /// Returns every card in a standard deck
+    #[staticmethod]
+    fn all_cards() -> Vec<PyCard> {
+        to_py_cards(Card::all_cards())
+    }
+
+    #[getter]
+    fn rank(&self) -> PyRank {
+        self.card.rank.into()
+    }
+
+    #[getter]
+    fn suit(&self) -> PySuit {
+        self.card.suit.into()
+    }
+
+    /// Returns the English name, e.g. "Ace of Spades"
+    fn name(&self) -> String {
+        self.card.name()
+    }
+
+    /// Returns the ordinal of the card in a standard deck
+    fn ordinal(&self) -> usize {
+        self.card.ordinal()
+    }
+
+    fn __str__(&self) -> String {
+        self.card.to_str()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Card('{}')", self.card.to_str())
+    }
+
+    fn __hash__(&self) -> u64 {
+        self.card.ordinal() as u64
+    }
+
+    fn __richcmp__(&self, other: PyRef<PyCard>, op: CompareOp) -> bool {
+        op.matches(self.card.cmp_rank_then_suit(&other.card))
+    }
+
+    fn __reduce__(slf: &Bound<PyCard>) -> (PyObject, (String,)) {
+        let cls = slf.get_type().into_any().unbind();
+        (cls, (slf.get().card.to_str(),))
+    }
+}
+
+/// An iterator over cards
+#[pyclass(module = "deckofcards")]
+pub struct CardIterator {
+    cards: alloc::vec::IntoIter<Card>,
+}
+
+#[pymethods]
+impl CardIterator {
+    fn __iter__(slf: PyRef<CardIterator>) -> PyRef<CardIterator> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<PyCard> {
+        self.cards.next().map(|card| PyCard { card })
+    }
+}
+
+/// A deck of cards, see `Deck`
+#[pyclass(name = "Deck", module = "deckofcards")]
+pub struct PyDeck {
+    pub deck: Deck<Card>,
+}
+
+#[pymethods]
+impl PyDeck {
+    /// Creates a standard deck of 52 cards, or a deck with the undealt cards from the bottom to
+    /// the top and the dealt cards
+    #[new]
+    #[pyo3(signature = (cards=None, dealt=None))]
+    fn new(cards: Option<Vec<String>>, dealt: Option<Vec<String>>) -> PyResult<PyDeck> {
+        let mut deck = match cards {
+            Some(_) => Deck::from_cards(&to_cards(cards)?),
+            None => Deck::new(),
+        };
+        deck.dealt_cards = to_cards(dealt)?;
+        Ok(PyDeck { deck })
+    }
+
+    /// Shuffles the undealt cards, into the same order as `Cards::seeded_shuffle` if there is a
+    /// seed
+    #[pyo3(signature = (seed=None))]
+    fn shuffle(&mut self, seed: Option<u64>) {
+        match seed {
+            Some(seed) => self.deck.seeded_shuffle(seed),
+            None => self.deck.shuffle(),
+        }
+    }
+
+    /// Deals the top card
+    fn deal_one(&mut self) -> PyResult<PyCard> {
+        let card = self.deck.deal_one().map_err(PyIndexError::new_err)?;
+        Ok(PyCard { card })
+    }
+
+    /// Deals up to the number of cards from the top
+    fn deal(&mut self, count: usize) -> Vec<PyCard> {
+        to_py_cards(&self.deck.deal(count))
+    }
+
+    /// Puts the dealt cards back on top of the deck, where they are dealt again first and in the
+    /// same order
+    fn reset(&mut self) {
+        self.deck.reset();
+    }
+
+    /// Returns the cards that have been dealt
+    #[getter]
+    fn dealt_cards(&self) -> Vec<PyCard> {
+        to_py_cards(&self.deck.dealt_cards)
+    }
+
+    /// Returns the number of undealt cards
+    fn __len__(&self) -> usize {
+        self.deck.undealt_count()
+    }
+
+    /// Iterates over the undealt cards in the order they would be dealt
+    fn __iter__(&self) -> CardIterator {
+        let mut cards = self.deck.cards.clone();
+        cards.reverse();
+        CardIterator {
+            cards: cards.into_iter(),
+        }
+    }
+
+    fn __contains__(&self, card: PyRef<PyCard>) -> bool {
+        self.deck.cards.contains(&card.card)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Deck({} undealt, {} dealt)",
+            self.deck.undealt_count(),
+            self.deck.dealt_count()
+        )
+    }
+
+    fn __reduce__(slf: &Bound<PyDeck>) -> (PyObject, (Vec<String>, Vec<String>)) {
+        let cls = slf.get_type().into_any().unbind();
+        let deck = &slf.borrow().deck;
+        (
+            cls,
+            (to_strings(&deck.cards), to_strings(&deck.dealt_cards)),
+        )
+    }
+}
+
+/// A hand of cards, see `Hand`
+#[pyclass(name = "Hand", module = "deckofcards")]
+pub struct PyHand {
+    pub hand: Hand,
+}
+
+#[pymethods]
+impl PyHand {
+    /// Creates a hand holding the cards
+    #[new]
+    #[pyo3(signature = (cards=None))]
+    fn new(cards: Option<Vec<String>>) -> PyResult<PyHand> {
+        Ok(PyHand {
+            hand: Hand::from_cards(&to_cards(cards)?),
+        })
+    }
+
+    /// Adds a card
+    fn push(&mut self, card: PyRef<PyCard>) {
+        self.hand.push_card(card.card);
+    }
+
+    /// Removes the first matching card, returning false if there is none
+    fn remove(&mut self, card: PyRef<PyCard>) -> bool {
+        self.hand.remove_card(&card.card)
+    }
+
+    /// Removes every card
+    fn clear(&mut self) {
+        self.hand.clear();
+    }
+
+    /// Shuffles the cards, into the same order as `Cards::seeded_shuffle` if there is a seed
+    #[pyo3(signature = (seed=None))]
+    fn shuffle(&mut self, seed: Option<u64>) {
+        match seed {
+            Some(seed) => self.hand.seeded_shuffle(seed),
+            None => self.hand.shuffle(),
+        }
+    }
+
+    /// Sorts the cards by rank and then suit, from low to high
+    fn sort(&mut self) {
+        self.hand.cards.sort_by(|a, b| a.cmp_rank_then_suit(b));
+    }
+
+    fn __len__(&self) -> usize {
+        self.hand.len()
+    }
+
+    fn __iter__(&self) -> CardIterator {
+        CardIterator {
+            cards: self.hand.cards.clone().into_iter(),
+        }
+    }
+
+    fn __getitem__(&self, index: isize) -> PyResult<PyCard> {
+        let len = self.hand.len() as isize;
+        let index = if index < 0 { index + len } else { index };
+        if index < 0 || index >= len {
+            return Err(PyIndexError::new_err("Hand index out of range"));
+        }
+        Ok(PyCard {
+            card: self.hand.cards[index as usize],
+        })
+    }
+
+    fn __contains__(&self, card: PyRef<PyCard>) -> bool {
+        self.hand.cards.contains(&card.card)
+    }
+
+    fn __str__(&self) -> String {
+        to_strings(&self.hand.cards).join(" ")
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Hand({:?})", to_strings(&self.hand.cards))
+    }
+
+    fn __reduce__(slf: &Bound<PyHand>) -> (PyObject, (Vec<String>,)) {
+        let cls = slf.get_type().into_any().unbind();
+        (cls, (to_strings(&slf.borrow().hand.cards),))
+    }
+}
+
+/// The `deckofcards` Python module
+#[pymodule]
+#[pyo3(name = "deckofcards")]
+pub fn init(m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<PyRank>()?;
+    m.add_class::<PySuit>()?;
+    m.add_class::<PyCard>()?;
+    m.add_class::<PyDeck>()?;
+    m.add_class::<PyHand>()?;
+    m.add_class::<CardIterator>()?;
+    Ok(())
+}
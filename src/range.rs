@@ -0,0 +1,435 @@
+//! Poker hand ranges such as "TT+, AKs, A2s-A5s, KQo:0.5".
+//!
+//! A `Range` is a set of two card starting hands, each with a weight between 0 and 1. The usual
+//! shorthands are understood:
+//!
+//! * "TT" is every combination of a pair, 6 combos
+//! * "AKs" is every suited combination, 4 combos, and "AKo" every offsuit one, 12 combos
+//! * "AK" is both suited and offsuit, 16 combos
+//! * "TT+" is TT and every higher pair, "A2s+" is A2s up to AKs
+//! * "TT-77" and "A5s-A2s" are everything between the two, in either order
+//! * "AhKh" is that exact combination
+//! * ":0.5" after any of the above gives it a weight of 0.5
+//!
+//! A range can be written back out in compact notation with `to_string()`.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt;
+use core::str::FromStr;
+
+use super::notation::{self, ParseError, Strictness};
+use super::*;
+
+/// A `Combo` is a two card starting hand. The higher card is always first.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Combo(pub Card, pub Card);
+
+impl Combo {
+    /// Creates a combo from two cards in any order
+    pub fn new(a: Card, b: Card) -> Combo {
+        if a >= b {
+            Combo(a, b)
+        } else {
+            Combo(b, a)
+        }
+    }
+
+    /// Tests if both cards are the same rank
+    pub fn is_pair(&self) -> bool {
+        self.0.rank == self.1.rank
+    }
+
+    /// Tests if both cards are the same suit
+    pub fn is_suited(&self) -> bool {
+        self.0.suit == self.1.suit
+    }
+}
+
+impl fmt::Display for Combo {
+    /// Writes the combo with lowercase suits, e.g. "AhKh"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for card in [self.0, self.1] {
+            write!(
+                f,
+                "{}{}",
+                card.rank.to_char(),
+                card.suit.to_char().to_ascii_lowercase()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether a hand class is suited, offsuit or both
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Kind {
+    Pair,
+    Suited,
+    Offsuit,
+    Any,
+}
+
+/// A `Range` is a set of weighted combos
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Range {
+    combos: BTreeMap<Combo, f64>,
+}
+
+impl Range {
+    /// Creates an empty range
+    pub fn new() -> Range {
+        Range::default()
+    }
+
+    /// Adds the combo with the weight, replacing any existing weight. A weight of zero removes
+    /// the combo.
+    pub fn insert(&mut self, combo: Combo, weight: f64) {
+        if weight > 0.0 {
+            let _ = self.combos.insert(combo, weight);
+        } else {
+            let _ = self.combos.remove(&combo);
+        }
+    }
+
+    /// Removes the combo from the range
+    pub fn remove(&mut self, combo: &Combo) -> bool {
+        self.combos.remove(combo).is_some()
+    }
+
+    /// Tests if the combo is in the range
+    pub fn contains(&self, combo: &Combo) -> bool {
+        self.combos.contains_key(combo)
+    }
+
+    /// Returns the weight of the combo, or 0 if it is not in the range
+    pub fn weight(&self, combo: &Combo) -> f64 {
+        self.combos.get(combo).copied().unwrap_or_default()
+    }
+
+    /// Returns the number of combos in the range
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    /// Tests if the range has no combos
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// Returns the sum of the weights of every combo
+    pub fn weighted_len(&self) -> f64 {
+        self.combos.values().sum()
+    }
+
+    /// Returns an iterator through the combos and their weights
+    pub fn combos(&self) -> impl Iterator<Item = (&Combo, f64)> {
+        self.combos.iter().map(|(combo, weight)| (combo, *weight))
+    }
+}
+
+impl FromStr for Range {
+    type Err = ParseError;
+
+    /// Parses a comma separated list of hands, classes and ranges
+    fn from_str(s: &str) -> Result<Range, ParseError> {
+        let mut range = Range::new();
+        let mut offset = 0;
+        for token in s.split(',') {
+            let start = offset + token.len() - token.trim_start().len();
+            offset += token.len() + 1;
+            let token = token.trim();
+            if token.is_empty() {
+                continue;
+            }
+            let error = |position: usize, reason| ParseError {
+                position: start + position,
+                reason,
+            };
+            let (body, weight) = match token.split_once(':') {
+                Some((body, weight)) => {
+                    let weight = weight
+                        .trim()
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|w| (0.0..=1.0).contains(w))
+                        .ok_or(error(body.len() + 1, "Invalid weight"))?;
+                    (body.trim_end(), weight)
+                }
+                None => (token, 1.0),
+            };
+            for combo in parse_token(body).map_err(|(p, reason)| error(p, reason))? {
+                range.insert(combo, weight);
+            }
+        }
+        Ok(range)
+    }
+}
+
+impl fmt::Display for Range {
+    /// Writes the range in compact notation
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut tokens: Vec<String> = Vec::new();
+        let mut covered: BTreeSet<Combo> = BTreeSet::new();
+        let push = |tokens: &mut Vec<String>, token: String, weight: f64| {
+            if weight < 1.0 {
+                tokens.push(format!("{}:{}", token, weight));
+            } else {
+                tokens.push(token);
+            }
+        };
+
+        // Pairs from the top down, runs reaching aces are written with a plus
+        let ranks = Rank::ranks();
+        let mut run: Vec<(Rank, f64)> = Vec::new();
+        for high in ranks.iter().rev() {
+            let weight = self.class_weight(*high, *high, Kind::Pair);
+            if let Some(w) = weight {
+                covered.extend(class_combos(*high, *high, Kind::Pair));
+                if run.last().is_some_and(|(_, rw)| *rw == w) {
+                    run.push((*high, w));
+                    continue;
+                }
+            }
+            if !run.is_empty() {
+                let (token, w) = pair_run_token(&run);
+                push(&mut tokens, token, w);
+                run.clear();
+            }
+            if let Some(w) = weight {
+                run.push((*high, w));
+            }
+        }
+        if !run.is_empty() {
+            let (token, w) = pair_run_token(&run);
+            push(&mut tokens, token, w);
+        }
+
+        // Other classes by their high card, kickers from the top down
+        for high in ranks.iter().rev() {
+            for kind in [Kind::Any, Kind::Suited, Kind::Offsuit] {
+                let mut run: Vec<(Rank, f64)> = Vec::new();
+                for low in ranks.iter().rev().filter(|r| *r < high) {
+                    let weight = self.class_weight(*high, *low, kind).filter(|w| {
+                        // A class written as "AK" must not also be written as "AKs"
+                        kind == Kind::Any || self.class_weight(*high, *low, Kind::Any) != Some(*w)
+                    });
+                    if let Some(w) = weight {
+                        covered.extend(class_combos(*high, *low, kind));
+                        if run.last().is_some_and(|(_, rw)| *rw == w) {
+                            run.push((*low, w));
+                            continue;
+                        }
+                    }
+                    if !run.is_empty() {
+                        let (token, w) = kicker_run_token(*high, &run, kind);
+                        push(&mut tokens, token, w);
+                        run.clear();
+                    }
+                    if let Some(w) = weight {
+                        run.push((*low, w));
+                    }
+                }
+                if !run.is_empty() {
+                    let (token, w) = kicker_run_token(*high, &run, kind);
+                    push(&mut tokens, token, w);
+                }
+            }
+        }
+
+        // Anything left over is written as individual combos
+        for (combo, weight) in self.combos.iter().rev() {
+            if !covered.contains(combo) {
+                push(&mut tokens, combo.to_string(), *weight);
+            }
+        }
+
+        write!(f, "{}", tokens.join(", "))
+    }
+}
+
+impl Range {
+    /// Returns the weight of the class if every combo in it has the same weight
+    fn class_weight(&self, high: Rank, low: Rank, kind: Kind) -> Option<f64> {
+        let mut weights = class_combos(high, low, kind)
+            .into_iter()
+            .map(|combo| self.combos.get(&combo).copied());
+        let first = weights.next().flatten()?;
+        weights.all(|w| w == Some(first)).then_some(first)
+    }
+}
+
+fn pair_run_token(run: &[(Rank, f64)]) -> (String, f64) {
+    let (top, weight) = run[0];
+    let (bottom, _) = run[run.len() - 1];
+    let pair = |r: Rank| format!("{}{}", r.to_char(), r.to_char());
+    let token = if run.len() == 1 {
+        pair(top)
+    } else if top == Rank::Ace {
+        format!("{}+", pair(bottom))
+    } else {
+        format!("{}-{}", pair(top), pair(bottom))
+    };
+    (token, weight)
+}
+
+fn kicker_run_token(high: Rank, run: &[(Rank, f64)], kind: Kind) -> (String, f64) {
+    let (top, weight) = run[0];
+    let (bottom, _) = run[run.len() - 1];
+    let suffix = match kind {
+        Kind::Suited => "s",
+        Kind::Offsuit => "o",
+        _ => "",
+    };
+    let class = |low: Rank| format!("{}{}{}", high.to_char(), low.to_char(), suffix);
+    let token = if run.len() == 1 {
+        class(top)
+    } else if top.ordinal() + 1 == high.ordinal() {
+        format!("{}+", class(bottom))
+    } else {
+        format!("{}-{}", class(top), class(bottom))
+    };
+    (token, weight)
+}
+
+/// Returns every combo in the hand class
+fn class_combos(high: Rank, low: Rank, kind: Kind) -> Vec<Combo> {
+    let mut combos = Vec::new();
+    let suits = Suit::suits();
+    for (i, s1) in suits.iter().enumerate() {
+        for (j, s2) in suits.iter().enumerate() {
+            let wanted = match kind {
+                Kind::Pair => i < j,
+                Kind::Suited => i == j,
+                Kind::Offsuit => i != j,
+                Kind::Any => true,
+            };
+            if wanted {
+                combos.push(Combo::new(Card::new(high, *s1), Card::new(low, *s2)));
+            }
+        }
+    }
+    combos
+}
+
+/// A hand class such as "AKs" with an optional trailing plus
+struct Class {
+    high: Rank,
+    low: Rank,
+    kind: Kind,
+    plus: bool,
+}
+
+/// Parses a single token, without its weight, into combos
+fn parse_token(s: &str) -> Result<Vec<Combo>, (usize, &'static str)> {
+    // An exact combo such as "AhKh"
+    if s.len() == 4 && s.is_char_boundary(2) {
+        let first = notation::parse_card(&s[..2], Strictness::Lenient);
+        let second = notation::parse_card(&s[2..], Strictness::Lenient);
+        if let (Ok(a), Ok(b)) = (first, second) {
+            if a == b {
+                return Err((2, "Duplicate card"));
+            }
+            return Ok(vec![Combo::new(a, b)]);
+        }
+    }
+
+    if let Some((from, to)) = s.split_once('-') {
+        let from = parse_class(from, 0)?;
+        let to = parse_class(to, from_len(s))?;
+        if from.plus || to.plus {
+            return Err((0, "Cannot use + in a range"));
+        }
+        if from.kind != to.kind {
+            return Err((from_len(s), "Range must be the same kind of hand"));
+        }
+        let mut combos = Vec::new();
+        if from.kind == Kind::Pair {
+            let (lo, hi) = order(from.high, to.high);
+            for rank in Rank::iterator().filter(|r| **r >= lo && **r <= hi) {
+                combos.extend(class_combos(*rank, *rank, Kind::Pair));
+            }
+        } else {
+            if from.high != to.high {
+                return Err((from_len(s), "Range must have the same first card"));
+            }
+            let (lo, hi) = order(from.low, to.low);
+            for rank in Rank::iterator().filter(|r| **r >= lo && **r <= hi) {
+                combos.extend(class_combos(from.high, *rank, from.kind));
+            }
+        }
+        return Ok(combos);
+    }
+
+    let class = parse_class(s, 0)?;
+    let top = if !class.plus {
+        class.low
+    } else if class.kind == Kind::Pair {
+        Rank::Ace
+    } else {
+        Rank::ranks()[class.high.ordinal() - 1]
+    };
+    let mut combos = Vec::new();
+    for rank in Rank::iterator().filter(|r| **r >= class.low && **r <= top) {
+        let high = if class.kind == Kind::Pair {
+            *rank
+        } else {
+            class.high
+        };
+        combos.extend(class_combos(high, *rank, class.kind));
+    }
+    Ok(combos)
+}
+
+/// Parses a hand class such as "AKs", "TT" or "A2s+", with `at` as the offset for errors
+fn parse_class(s: &str, at: usize) -> Result<Class, (usize, &'static str)> {
+    let chars = s.chars().collect::<Vec<_>>();
+    // The byte offset of the char at an index, or of the end of the class
+    let offset = |i: usize| at + s.char_indices().nth(i).map_or(s.len(), |(o, _)| o);
+    let rank = |i: usize| {
+        chars
+            .get(i)
+            .and_then(|c| Rank::from_char(c.to_ascii_uppercase()).ok())
+            .ok_or((offset(i), "Invalid rank"))
+    };
+    let (a, b) = (rank(0)?, rank(1)?);
+    let (high, low) = if a >= b { (a, b) } else { (b, a) };
+    let mut i = 2;
+    let kind = match chars.get(i).map(|c| c.to_ascii_lowercase()) {
+        Some('s') if high != low => {
+            i += 1;
+            Kind::Suited
+        }
+        Some('o') if high != low => {
+            i += 1;
+            Kind::Offsuit
+        }
+        _ if high == low => Kind::Pair,
+        _ => Kind::Any,
+    };
+    let plus = chars.get(i) == Some(&'+');
+    if plus {
+        i += 1;
+    }
+    if i < chars.len() {
+        return Err((offset(i), "Unexpected characters"));
+    }
+    Ok(Class {
+        high,
+        low,
+        kind,
+        plus,
+    })
+}
+
+/// Returns the byte offset of the second half of a dashed range
+fn from_len(s: &str) -> usize {
+    s.find('-').map(|i| i + 1).unwrap_or_default()
+}
+
+fn order(a: Rank, b: Rank) -> (Rank, Rank) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
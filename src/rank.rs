@@ -1,10 +1,10 @@
-use std::cmp::Ordering;
-use std::slice::Iter;
+use core::cmp::Ordering;
+use core::slice::Iter;
 
 use self::Rank::*;
 
 /// This enumeration holds the ranks in a standard deck of cards.
-#[derive(Copy, Clone, Hash, Eq, PartialEq, PartialOrd, Debug)]
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
 pub enum Rank {
     Two,
     Three,
@@ -34,10 +34,16 @@ impl Ord for Rank {
     }
 }
 
+impl PartialOrd for Rank {
+    fn partial_cmp(&self, other: &Rank) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Rank {
     /// Returns an iterator through the standard ranks
     pub fn iterator() -> Iter<'static, Rank> {
-        Rank::ranks().into_iter()
+        Rank::ranks().iter()
     }
 
     /// Returns an ordinal for the rank.
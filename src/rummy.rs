@@ -0,0 +1,349 @@
+//! Meld detection and scoring for Gin Rummy.
+//!
+//! A meld is either a set of three or four cards of the same `Rank`, or a run of three or more
+//! cards of the same `Suit` in consecutive rank order. Aces are low in a run, so A-2-3 is a run
+//! but Q-K-A is not. Any card which is not part of a meld is deadwood.
+
+use core::cmp::Ordering;
+
+use super::*;
+
+/// The most deadwood a player may hold and still knock
+pub const KNOCK_LIMIT: usize = 10;
+
+/// The bonus awarded for going gin
+pub const GIN_BONUS: usize = 25;
+
+/// The bonus awarded to the opponent for undercutting the knocker
+pub const UNDERCUT_BONUS: usize = 25;
+
+/// The most cards a hand may hold to be searched for melds. The search grows quickly with the
+/// number of cards, and a hand of Gin Rummy holds at most 11.
+pub const MAX_MELD_CARDS: usize = 16;
+
+/// A `Meld` is a group of cards which do not count as deadwood.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Meld {
+    /// Three or four cards of the same rank, sorted by suit
+    Set(Vec<Card>),
+    /// Three or more cards of the same suit in consecutive rank order, sorted ace low
+    Run(Vec<Card>),
+}
+
+impl Meld {
+    /// Returns the cards in the meld
+    pub fn cards(&self) -> &[Card] {
+        match self {
+            Meld::Set(cards) | Meld::Run(cards) => cards,
+        }
+    }
+
+    /// Tests if the card can be laid off onto this meld. Nothing can be laid off onto an empty
+    /// meld.
+    pub fn accepts(&self, card: &Card) -> bool {
+        match self {
+            Meld::Set(cards) => {
+                cards.len() < 4
+                    && cards.first().is_some_and(|c| c.rank == card.rank)
+                    && !cards.iter().any(|c| c.suit == card.suit)
+            }
+            Meld::Run(cards) => match (cards.first(), cards.last()) {
+                (Some(first), Some(last)) => {
+                    let low = ace_low_ordinal(first.rank);
+                    let high = ace_low_ordinal(last.rank);
+                    let value = ace_low_ordinal(card.rank);
+                    first.suit == card.suit && (value + 1 == low || value == high + 1)
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Adds the card to the meld, keeping it sorted. Returns false if the card does not fit.
+    pub fn lay_off(&mut self, card: Card) -> bool {
+        if !self.accepts(&card) {
+            return false;
+        }
+        match self {
+            Meld::Set(cards) => {
+                cards.push(card);
+                cards.sort_by(|a, b| a.cmp_suit_then_rank(b));
+            }
+            Meld::Run(cards) => {
+                cards.push(card);
+                cards.sort_by(|a, b| a.rank.cmp_ace_low(&b.rank));
+            }
+        }
+        true
+    }
+}
+
+/// The result of dividing a hand into melds and deadwood
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Melding {
+    /// The melds that were found
+    pub melds: Vec<Meld>,
+    /// The cards which are not part of any meld
+    pub deadwood: Vec<Card>,
+}
+
+impl Melding {
+    /// Returns the total value of the deadwood
+    pub fn deadwood_value(&self) -> usize {
+        self.deadwood.iter().map(deadwood_value).sum()
+    }
+
+    /// Tests if every card is part of a meld
+    pub fn is_gin(&self) -> bool {
+        self.deadwood.is_empty()
+    }
+}
+
+/// How a hand of Gin Rummy ended after a player knocked
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KnockOutcome {
+    /// The knocker melded every card
+    Gin,
+    /// The knocker had less deadwood than the opponent
+    Knock,
+    /// The opponent had the same or less deadwood than the knocker
+    Undercut,
+}
+
+/// The score for a hand of Gin Rummy that ended in a knock
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KnockScore {
+    /// How the hand ended
+    pub outcome: KnockOutcome,
+    /// The knocker's melds and deadwood
+    pub knocker: Melding,
+    /// The opponent's melds and deadwood after laying off onto the knocker's melds
+    pub opponent: Melding,
+    /// The points awarded, to the knocker unless the outcome is `Undercut`
+    pub points: usize,
+}
+
+/// Returns the deadwood value of a card. Aces count 1, court cards count 10, other cards count
+/// their pip value.
+pub fn deadwood_value(card: &Card) -> usize {
+    match card.rank {
+        Rank::Ace => 1,
+        Rank::Jack | Rank::Queen | Rank::King => 10,
+        rank => rank.ordinal() + 2,
+    }
+}
+
+/// Divides the hand into the melds which leave the least deadwood value. Fails if the hand holds
+/// more than `MAX_MELD_CARDS` cards.
+pub fn best_melds(hand: &Hand) -> Result<Melding, &'static str> {
+    best_melding(hand.cards(), |melding| melding)
+}
+
+/// Lays off as many cards as possible onto the melds, returning the extended melds and the cards
+/// which could not be laid off.
+pub fn lay_off(melds: &[Meld], cards: &[Card]) -> (Vec<Meld>, Vec<Card>) {
+    let mut melds = melds.to_vec();
+    let mut remaining = cards.to_vec();
+    loop {
+        // Runs are tried first because extending a run can allow further cards to be laid off
+        let target = remaining.iter().enumerate().find_map(|(i, card)| {
+            melds
+                .iter()
+                .position(|m| matches!(m, Meld::Run(_)) && m.accepts(card))
+                .or_else(|| melds.iter().position(|m| m.accepts(card)))
+                .map(|meld| (i, meld))
+        });
+        match target {
+            Some((i, meld)) => {
+                let card = remaining.remove(i);
+                let _ = melds[meld].lay_off(card);
+            }
+            None => break,
+        }
+    }
+    (melds, remaining)
+}
+
+/// Scores a knock. The opponent arranges their hand to lay off as much as possible onto the
+/// knocker's melds, unless the knocker went gin. Fails if the knocker has too much deadwood or
+/// either hand holds too many cards to search.
+pub fn knock(knocker: &Hand, opponent: &Hand) -> Result<KnockScore, &'static str> {
+    let knocker = best_melds(knocker)?;
+    let knocker_deadwood = knocker.deadwood_value();
+    if knocker_deadwood > KNOCK_LIMIT {
+        return Err("Too much deadwood to knock");
+    }
+
+    if knocker.is_gin() {
+        let opponent = best_melds(opponent)?;
+        let points = GIN_BONUS + opponent.deadwood_value();
+        return Ok(KnockScore {
+            outcome: KnockOutcome::Gin,
+            knocker,
+            opponent,
+            points,
+        });
+    }
+
+    let opponent = best_melding(opponent.cards(), |melding| {
+        let (_, deadwood) = lay_off(&knocker.melds, &melding.deadwood);
+        Melding {
+            melds: melding.melds,
+            deadwood,
+        }
+    })?;
+    let opponent_deadwood = opponent.deadwood_value();
+    let (outcome, points) = if opponent_deadwood <= knocker_deadwood {
+        (
+            KnockOutcome::Undercut,
+            UNDERCUT_BONUS + knocker_deadwood - opponent_deadwood,
+        )
+    } else {
+        (KnockOutcome::Knock, opponent_deadwood - knocker_deadwood)
+    };
+    Ok(KnockScore {
+        outcome,
+        knocker,
+        opponent,
+        points,
+    })
+}
+
+/// Returns the rank ordinal with the ace counted as the lowest card
+fn ace_low_ordinal(rank: Rank) -> usize {
+    match rank {
+        Rank::Ace => 0,
+        rank => rank.ordinal() + 1,
+    }
+}
+
+/// A meld candidate, as a bit mask of card indices and the meld itself
+type Candidate = (u64, Meld);
+
+/// Finds every possible set and run within the cards
+fn candidate_melds(cards: &[Card]) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    let mask = |indices: &[usize]| indices.iter().fold(0u64, |m, i| m | (1 << i));
+    let meld_cards = |indices: &[usize]| indices.iter().map(|i| cards[*i]).collect::<Vec<_>>();
+
+    // Sets use one card of each suit for the rank
+    for rank in Rank::iterator() {
+        let mut indices: Vec<usize> = Vec::with_capacity(4);
+        for suit in Suit::iterator() {
+            if let Some(i) = cards
+                .iter()
+                .position(|c| c.rank == *rank && c.suit == *suit)
+            {
+                indices.push(i);
+            }
+        }
+        if indices.len() >= 3 {
+            candidates.push((mask(&indices), Meld::Set(meld_cards(&indices))));
+        }
+        if indices.len() == 4 {
+            // Any three of the four may be melded, leaving the fourth free for a run
+            for skip in 0..4 {
+                let mut subset = indices.clone();
+                let _ = subset.remove(skip);
+                candidates.push((mask(&subset), Meld::Set(meld_cards(&subset))));
+            }
+        }
+    }
+
+    // Runs are every consecutive sequence of 3 or more in a suit
+    for suit in Suit::iterator() {
+        let mut by_rank: [Option<usize>; 13] = [None; 13];
+        for (i, card) in cards.iter().enumerate() {
+            let slot = &mut by_rank[ace_low_ordinal(card.rank)];
+            if card.suit == *suit && slot.is_none() {
+                *slot = Some(i);
+            }
+        }
+        for start in 0..by_rank.len() {
+            let mut indices: Vec<usize> = Vec::new();
+            for slot in by_rank.iter().skip(start) {
+                match slot {
+                    Some(i) => indices.push(*i),
+                    None => break,
+                }
+                if indices.len() >= 3 {
+                    candidates.push((mask(&indices), Meld::Run(meld_cards(&indices))));
+                }
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Searches every way of dividing the cards into melds and deadwood. Each division is passed
+/// through `adjust`, and the one with the least deadwood value afterwards is returned.
+fn best_melding<F>(cards: &[Card], adjust: F) -> Result<Melding, &'static str>
+where
+    F: Fn(Melding) -> Melding,
+{
+    if cards.len() > MAX_MELD_CARDS {
+        return Err("Too many cards to search for melds");
+    }
+    let candidates = candidate_melds(cards);
+    let mut chosen: Vec<usize> = Vec::new();
+    let mut best: Option<Melding> = None;
+    search(
+        cards,
+        &candidates,
+        0,
+        0,
+        &mut chosen,
+        &mut |deadwood_mask, chosen| {
+            let melding = adjust(Melding {
+                melds: chosen.iter().map(|i| candidates[*i].1.clone()).collect(),
+                deadwood: (0..cards.len())
+                    .filter(|i| deadwood_mask & (1 << i) != 0)
+                    .map(|i| cards[i])
+                    .collect(),
+            });
+            let better = match &best {
+                Some(best) => match melding.deadwood_value().cmp(&best.deadwood_value()) {
+                    Ordering::Less => true,
+                    Ordering::Equal => melding.deadwood.len() < best.deadwood.len(),
+                    Ordering::Greater => false,
+                },
+                None => true,
+            };
+            if better {
+                best = Some(melding);
+            }
+        },
+    );
+    let mut best = best.unwrap_or_default();
+    best.deadwood.sort_by(|a, b| a.cmp_suit_then_rank(b));
+    Ok(best)
+}
+
+/// Recursively assigns the lowest unassigned card either to deadwood or to each meld that can
+/// still hold it, calling `visit` with the deadwood mask and chosen melds for each division.
+fn search<F>(
+    cards: &[Card],
+    candidates: &[Candidate],
+    used: u64,
+    deadwood: u64,
+    chosen: &mut Vec<usize>,
+    visit: &mut F,
+) where
+    F: FnMut(u64, &[usize]),
+{
+    let next = (0..cards.len()).find(|i| used & (1 << i) == 0);
+    let Some(next) = next else {
+        visit(deadwood, chosen);
+        return;
+    };
+    let bit = 1u64 << next;
+    for (i, (mask, _)) in candidates.iter().enumerate() {
+        if mask & bit != 0 && mask & used == 0 {
+            chosen.push(i);
+            search(cards, candidates, used | mask, deadwood, chosen, visit);
+            let _ = chosen.pop();
+        }
+    }
+    search(cards, candidates, used | bit, deadwood | bit, chosen, visit);
+}
@@ -0,0 +1,455 @@
+//! A server for playing a game at one table from several clients.
+//!
+//! Clients connect over TCP and send and receive one JSON object per line. A client joins a seat,
+//! takes actions for it and is sent an event for every action taken at the table, followed by the
+//! table as its seat sees it. The server holds the only copy of the game, so a client is never
+//! sent the deck or the cards in another player's hand, only how many there are.
+//!
+//! Messages from a client:
+//!
+//! * `{"type":"join","name":"Ann"}` sits at the first free seat, or at the seat given by `"seat"`
+//! * `{"type":"act","action":...}` takes an action for the client's seat
+//! * `{"type":"state"}` asks for the table again
+//! * `{"type":"leave"}` gives up the seat
+//!
+//! Messages from the server:
+//!
+//! * `{"type":"joined","seat":0}` answers a join
+//! * `{"type":"event","seat":0,"player":"Ann","action":...}` tells every seat about an action
+//! * `{"type":"state","seat":0,"hand":[...],...}` is the table as the seat sees it, with `null`
+//!   in place of every card the seat cannot see
+//! * `{"type":"left","seat":0}` tells every seat a player has left
+//! * `{"type":"error","message":"..."}` answers a message that could not be carried out
+//!
+//! Zones are written as `"stock"`, `"discard"`, `"board"`, `{"hand":N}` or `{"area":N}`.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use super::blackjack::{self, Blackjack};
+use super::game::{Game, Rules, Zone};
+use super::hearts::{self, Hearts};
+use super::solitaire::{self, Klondike};
+use super::*;
+
+/// How long a client may take to accept a message before it is disconnected
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A message for the client with the given id
+pub type Outgoing = (usize, Value);
+
+/// A game that can be played through the server
+pub trait Protocol: Rules<Card = Card> {
+    /// Reads an action sent by a client
+    fn parse_action(value: &Value) -> Result<Self::Action, &'static str>;
+
+    /// Writes an action in the form `parse_action` reads. Actions are sent to every seat, so they
+    /// must not contain anything only the seat taking them may know.
+    fn action_json(action: &Self::Action) -> Value;
+
+    /// Writes the state of the rules that every player may see, e.g. the scores
+    fn rules_json(&self) -> Value {
+        Value::Null
+    }
+}
+
+/// Writes a zone
+pub fn zone_json(zone: Zone) -> Value {
+    match zone {
+        Zone::Hand(seat) => json!({ "hand": seat }),
+        Zone::Stock => json!("stock"),
+        Zone::Discard => json!("discard"),
+        Zone::Board => json!("board"),
+        Zone::Area(area) => json!({ "area": area }),
+    }
+}
+
+/// Reads a zone written by `zone_json`
+pub fn parse_zone(value: &Value) -> Result<Zone, &'static str> {
+    let index = |v: &Value| {
+        v.as_u64()
+            .map(|i| i as usize)
+            .ok_or("Zone index is not a number")
+    };
+    match value {
+        Value::String(s) if s == "stock" => Ok(Zone::Stock),
+        Value::String(s) if s == "discard" => Ok(Zone::Discard),
+        Value::String(s) if s == "board" => Ok(Zone::Board),
+        Value::Object(o) if o.contains_key("hand") => Ok(Zone::Hand(index(&o["hand"])?)),
+        Value::Object(o) if o.contains_key("area") => Ok(Zone::Area(index(&o["area"])?)),
+        _ => Err("Unknown zone"),
+    }
+}
+
+fn parse_card(value: &Value) -> Result<Card, &'static str> {
+    Card::from_str(value.as_str().ok_or("Card is not a string")?)
+}
+
+/// The server side of a table, which turns the messages of clients into actions and decides what
+/// each client is told. It does no networking itself, see `serve`.
+pub struct Server<R: Protocol> {
+    game: Game<R>,
+    players: Vec<Option<(usize, String)>>,
+}
+
+impl<R> Server<R>
+where
+    R: Protocol,
+{
+    /// Creates a server for the game with every seat free
+    pub fn new(game: Game<R>) -> Self {
+        let players = vec![None; game.table.seats.len()];
+        Server { game, players }
+    }
+
+    /// Returns the game being played
+    pub fn game(&self) -> &Game<R> {
+        &self.game
+    }
+
+    /// Returns the seat the client has joined, if any
+    pub fn seat_of(&self, client: usize) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|p| matches!(p, Some((c, _)) if *c == client))
+    }
+
+    /// Handles a line sent by the client and returns the messages to send in reply
+    pub fn handle(&mut self, client: usize, line: &str) -> Vec<Outgoing> {
+        match self.request(client, line) {
+            Ok(messages) => messages,
+            Err(message) => vec![(client, json!({ "type": "error", "message": message }))],
+        }
+    }
+
+    /// Frees the client's seat, e.g. when it disconnects, and returns the messages to send
+    pub fn leave(&mut self, client: usize) -> Vec<Outgoing> {
+        let seat = match self.seat_of(client) {
+            Some(seat) => seat,
+            None => return Vec::new(),
+        };
+        self.players[seat] = None;
+        let mut messages = self.broadcast(&json!({ "type": "left", "seat": seat }));
+        messages.extend(self.states());
+        messages
+    }
+
+    /// Returns the table as the seat sees it, or as someone who is not playing sees it
+    pub fn view(&self, seat: Option<usize>) -> Value {
+        let table = &self.game.table;
+        let info = self.game.info_set(seat, Card::all_cards());
+        let shown = |zone: Zone| -> Vec<Value> {
+            info.zones[&zone]
+                .iter()
+                .map(|card| card.map_or(Value::Null, |c| json!(c.to_str())))
+                .collect()
+        };
+        let seats = table
+            .seats
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                json!({
+                    "name": s.name,
+                    "player": self.players[i].as_ref().map(|(_, name)| name),
+                    "cards": shown(Zone::Hand(i)),
+                })
+            })
+            .collect::<Vec<_>>();
+        let areas = table
+            .areas
+            .keys()
+            .map(|zone| {
+                let name = match zone {
+                    Zone::Area(i) => table.area_names[*i].as_str(),
+                    Zone::Board => "Board",
+                    _ => "Discard",
+                };
+                json!({ "zone": zone_json(*zone), "name": name, "cards": shown(*zone) })
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "type": "state",
+            "seat": seat,
+            "hand": seat.map(|_| info.hand.iter().map(|c| c.to_str()).collect::<Vec<_>>()),
+            "turn": { "seat": info.turn.seat, "phase": format!("{:?}", info.turn.phase) },
+            "over": info.over,
+            "stock": info.stock,
+            "seats": seats,
+            "areas": areas,
+            "rules": self.game.rules.rules_json(),
+        })
+    }
+
+    fn request(&mut self, client: usize, line: &str) -> Result<Vec<Outgoing>, &'static str> {
+        let message: Value = serde_json::from_str(line).map_err(|_| "Message is not JSON")?;
+        match message["type"].as_str() {
+            Some("join") => self.join(client, &message),
+            Some("act") => self.act(client, &message["action"]),
+            Some("state") => Ok(vec![(client, self.view(self.seat_of(client)))]),
+            Some("leave") if self.seat_of(client).is_some() => Ok(self.leave(client)),
+            Some("leave") => Err("Not seated"),
+            _ => Err("Unknown message type"),
+        }
+    }
+
+    fn join(&mut self, client: usize, message: &Value) -> Result<Vec<Outgoing>, &'static str> {
+        if self.seat_of(client).is_some() {
+            return Err("Already seated");
+        }
+        let seat = match &message["seat"] {
+            Value::Null => self
+                .players
+                .iter()
+                .position(|p| p.is_none())
+                .ok_or("No free seat")?,
+            seat => {
+                let seat = seat.as_u64().ok_or("Seat is not a number")? as usize;
+                match self.players.get(seat) {
+                    Some(None) => seat,
+                    Some(Some(_)) => return Err("Seat is taken"),
+                    None => return Err("No such seat"),
+                }
+            }
+        };
+        let name = match message["name"].as_str() {
+            Some(name) => name.to_string(),
+            None => self.game.table.seats[seat].name.clone(),
+        };
+        self.players[seat] = Some((client, name));
+        let mut messages = vec![(client, json!({ "type": "joined", "seat": seat }))];
+        messages.extend(self.states());
+        Ok(messages)
+    }
+
+    fn act(&mut self, client: usize, action: &Value) -> Result<Vec<Outgoing>, &'static str> {
+        let seat = self.seat_of(client).ok_or("Join a seat first")?;
+        let action = R::parse_action(action)?;
+        self.game.act(seat, &action)?;
+        let name = self.players[seat].as_ref().map(|(_, name)| name.clone());
+        let event = json!({
+            "type": "event",
+            "seat": seat,
+            "player": name,
+            "action": R::action_json(&action),
+        });
+        let mut messages = self.broadcast(&event);
+        messages.extend(self.states());
+        Ok(messages)
+    }
+
+    fn broadcast(&self, message: &Value) -> Vec<Outgoing> {
+        self.players
+            .iter()
+            .flatten()
+            .map(|(client, _)| (*client, message.clone()))
+            .collect()
+    }
+
+    /// Sends every seated client the table as its seat sees it
+    fn states(&self) -> Vec<Outgoing> {
+        self.players
+            .iter()
+            .enumerate()
+            .filter_map(|(seat, p)| {
+                p.as_ref()
+                    .map(|(client, _)| (*client, self.view(Some(seat))))
+            })
+            .collect()
+    }
+}
+
+enum Incoming {
+    Connected(usize, TcpStream),
+    Line(usize, String),
+    Disconnected(usize),
+}
+
+/// Writes the messages for a client on its own thread, so a slow client does not hold up the game.
+/// A client that cannot be written to within `WRITE_TIMEOUT` is shut down, which disconnects it.
+fn writer(mut stream: TcpStream) -> mpsc::Sender<Value> {
+    let (sender, receiver) = mpsc::channel::<Value>();
+    let _ = thread::spawn(move || {
+        let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+        for message in receiver {
+            if writeln!(stream, "{}", message).is_err() {
+                let _ = stream.shutdown(Shutdown::Both);
+                return;
+            }
+        }
+    });
+    sender
+}
+
+/// Serves the table to clients connecting to the listener until the game is over and every
+/// client has disconnected. Each connection is read and written on threads of its own and the
+/// game is played on this one.
+///
+/// # Example
+///
+/// ```no_run
+/// use deckofcards::hearts::Hearts;
+/// use deckofcards::server::{self, Server};
+/// use std::net::TcpListener;
+///
+/// let listener = TcpListener::bind("127.0.0.1:7878").unwrap();
+/// server::serve(listener, Server::new(Hearts::deal(1))).unwrap();
+/// ```
+pub fn serve<R>(listener: TcpListener, mut server: Server<R>) -> io::Result<()>
+where
+    R: Protocol,
+{
+    let (sender, receiver) = mpsc::channel();
+    let _ = thread::spawn(move || {
+        for (client, stream) in listener.incoming().enumerate() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+            let reader = match stream.try_clone() {
+                Ok(reader) => reader,
+                Err(_) => continue,
+            };
+            if sender.send(Incoming::Connected(client, stream)).is_err() {
+                return;
+            }
+            let sender = sender.clone();
+            let _ = thread::spawn(move || {
+                for line in BufReader::new(reader).lines() {
+                    match line {
+                        Ok(line) if line.trim().is_empty() => {}
+                        Ok(line) => {
+                            if sender.send(Incoming::Line(client, line)).is_err() {
+                                return;
+                            }
+                        }
+                        Err(_) => break,
+                    }
+                }
+                let _ = sender.send(Incoming::Disconnected(client));
+            });
+        }
+    });
+
+    let mut streams = BTreeMap::new();
+    for incoming in receiver {
+        let messages = match incoming {
+            Incoming::Connected(client, stream) => {
+                let _ = streams.insert(client, writer(stream));
+                Vec::new()
+            }
+            Incoming::Line(client, line) => server.handle(client, &line),
+            Incoming::Disconnected(client) => {
+                let _ = streams.remove(&client);
+                server.leave(client)
+            }
+        };
+        for (client, message) in messages {
+            if let Some(stream) = streams.get(&client) {
+                // A client whose writer has stopped is dropped when its reader disconnects
+                let _ = stream.send(message);
+            }
+        }
+        if server.game().is_over() && streams.is_empty() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn parse_word<'a>(value: &'a Value, words: &[&str]) -> Result<&'a str, &'static str> {
+    match value.as_str() {
+        Some(word) if words.contains(&word) => Ok(word),
+        _ => Err("Unknown action"),
+    }
+}
+
+impl Protocol for Hearts {
+    /// Reads `{"play":"QS"}` or `"deal"`
+    fn parse_action(value: &Value) -> Result<hearts::Action, &'static str> {
+        match value.get("play") {
+            Some(card) => Ok(hearts::Action::Play(parse_card(card)?)),
+            None => parse_word(value, &["deal"]).map(|_| hearts::Action::Deal),
+        }
+    }
+
+    fn action_json(action: &hearts::Action) -> Value {
+        match action {
+            hearts::Action::Play(card) => json!({ "play": card.to_str() }),
+            hearts::Action::Deal => json!("deal"),
+        }
+    }
+
+    fn rules_json(&self) -> Value {
+        // The seed is left out since it would tell everyone the next deal
+        let trick = |trick: &[(usize, Card)]| -> Vec<Value> {
+            trick
+                .iter()
+                .map(|(seat, card)| json!({ "seat": seat, "card": card.to_str() }))
+                .collect()
+        };
+        json!({
+            "trick": trick(&self.trick),
+            "last_trick": trick(&self.last_trick),
+            "hearts_broken": self.hearts_broken,
+            "scores": self.scores,
+        })
+    }
+}
+
+impl Protocol for Blackjack {
+    /// Reads `"hit"`, `"stand"` or `"deal"`
+    fn parse_action(value: &Value) -> Result<blackjack::Action, &'static str> {
+        Ok(match parse_word(value, &["hit", "stand", "deal"])? {
+            "hit" => blackjack::Action::Hit,
+            "stand" => blackjack::Action::Stand,
+            _ => blackjack::Action::Deal,
+        })
+    }
+
+    fn action_json(action: &blackjack::Action) -> Value {
+        match action {
+            blackjack::Action::Hit => json!("hit"),
+            blackjack::Action::Stand => json!("stand"),
+            blackjack::Action::Deal => json!("deal"),
+        }
+    }
+
+    fn rules_json(&self) -> Value {
+        json!({
+            "outcome": self.outcome.map(|o| format!("{:?}", o)),
+            "wins": self.wins,
+            "losses": self.losses,
+            "pushes": self.pushes,
+        })
+    }
+}
+
+impl Protocol for Klondike {
+    /// Reads `"draw"` or `{"move":{"from":ZONE,"index":N,"to":ZONE}}`
+    fn parse_action(value: &Value) -> Result<solitaire::Action, &'static str> {
+        match value.get("move") {
+            Some(m) => Ok(solitaire::Action::Move {
+                from: parse_zone(&m["from"])?,
+                index: m["index"].as_u64().ok_or("Index is not a number")? as usize,
+                to: parse_zone(&m["to"])?,
+            }),
+            None => parse_word(value, &["draw"]).map(|_| solitaire::Action::Draw),
+        }
+    }
+
+    fn action_json(action: &solitaire::Action) -> Value {
+        match action {
+            solitaire::Action::Draw => json!("draw"),
+            solitaire::Action::Move { from, index, to } => json!({
+                "move": { "from": zone_json(*from), "index": index, "to": zone_json(*to) }
+            }),
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Klondike, the most common game of patience.
+//!
+//! The cards are dealt into seven tableau piles, the first with one card and the last with seven,
+//! with only the top card of each face up. The rest of the deck is the stock, which is turned one
+//! card at a time onto the waste. Cards are built down the tableau in alternating colors and up
+//! the four foundations by suit from the Ace. The game is won when every card is on a foundation.
+
+use super::game::{Game, Table, Transition, Turn, Zone};
+use super::*;
+
+/// The pile the stock is turned onto
+pub const WASTE: Zone = Zone::Area(0);
+
+/// The piles built up by suit from the Ace
+pub const FOUNDATIONS: [Zone; 4] = [Zone::Area(1), Zone::Area(2), Zone::Area(3), Zone::Area(4)];
+
+/// The piles built down in alternating colors
+pub const TABLEAU: [Zone; 7] = [
+    Zone::Area(5),
+    Zone::Area(6),
+    Zone::Area(7),
+    Zone::Area(8),
+    Zone::Area(9),
+    Zone::Area(10),
+    Zone::Area(11),
+];
+
+/// The actions in a game of Klondike
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Turns the top card of the stock onto the waste, or the waste back over into the stock if
+    /// the stock is empty
+    Draw,
+    /// Moves the card at the index in a pile, and every card on top of it, to another pile
+    Move {
+        /// The pile the cards are moved from
+        from: Zone,
+        /// The index of the lowest card to move
+        index: usize,
+        /// The pile the cards are moved to
+        to: Zone,
+    },
+}
+
+/// The rules of Klondike, which also track how many cards of each tableau pile are face down
+#[derive(Clone, Debug, Default)]
+pub struct Klondike {
+    /// The number of face down cards at the bottom of each tableau pile
+    pub face_down: [usize; 7],
+}
+
+impl Klondike {
+    /// Deals a new game from the deck
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use deckofcards::solitaire::{self, Action, Klondike};
+    /// use deckofcards::{Cards, Deck, Decky};
+    ///
+    /// let mut deck = Deck::new();
+    /// deck.seeded_shuffle(7);
+    /// let mut game = Klondike::deal(deck);
+    /// assert_eq!(game.table.cards(solitaire::TABLEAU[6]).unwrap().len(), 7);
+    /// assert!(game.act(0, &Action::Draw).is_ok());
+    /// assert_eq!(game.table.cards(solitaire::WASTE).unwrap().len(), 1);
+    /// ```
+    pub fn deal(deck: Deck<Card>) -> Game<Klondike> {
+        let mut table = Table::new(deck, &["Player"]);
+        let _ = table.add_area("Waste");
+        for i in 0..FOUNDATIONS.len() {
+            let _ = table.add_area(&format!("Foundation {}", i + 1));
+        }
+        let mut rules = Klondike::default();
+        for i in 0..TABLEAU.len() {
+            let _ = table.add_area(&format!("Tableau {}", i + 1));
+            rules.face_down[i] = i;
+        }
+        for i in 0..TABLEAU.len() {
+            for pile in TABLEAU.iter().skip(i) {
+                let _ = table.draw(*pile);
+            }
+        }
+        Game::new(table, rules).unwrap()
+    }
+
+    /// Returns the number of face down cards in the pile, which is 0 unless it is a tableau pile
+    pub fn face_down(&self, pile: Zone) -> usize {
+        TABLEAU
+            .iter()
+            .position(|z| *z == pile)
+            .map_or(0, |i| self.face_down[i])
+    }
+
+    /// Tests if every card is on a foundation
+    pub fn is_won(table: &Table) -> bool {
+        FOUNDATIONS
+            .iter()
+            .all(|f| table.cards(*f).is_some_and(|cards| cards.len() == 13))
+    }
+
+    /// Finds the foundation the card could be moved to, if any
+    pub fn foundation_for(table: &Table, card: &Card) -> Option<Zone> {
+        FOUNDATIONS
+            .iter()
+            .find(|f| builds_on_foundation(table.cards(**f).unwrap_or_default(), card))
+            .copied()
+    }
+}
+
+/// Returns the value of the rank with the Ace as 1 and the King as 13
+fn value(rank: Rank) -> usize {
+    match rank {
+        Rank::Ace => 1,
+        rank => rank.ordinal() + 2,
+    }
+}
+
+fn builds_on_foundation(pile: &[Card], card: &Card) -> bool {
+    match pile.last() {
+        None => card.rank == Rank::Ace,
+        Some(top) => top.suit == card.suit && value(card.rank) == value(top.rank) + 1,
+    }
+}
+
+fn builds_on_tableau(pile: &[Card], card: &Card) -> bool {
+    match pile.last() {
+        None => card.rank == Rank::King,
+        Some(top) => {
+            top.suit.to_color() != card.suit.to_color() && value(top.rank) == value(card.rank) + 1
+        }
+    }
+}
+
+impl game::Rules for Klondike {
+    type Card = Card;
+    type Hand = Hand;
+    type Phase = ();
+    type Action = Action;
+
+    fn initial_phase(&self) {}
+
+    fn validate(
+        &self,
+        table: &Table,
+        _turn: &Turn<()>,
+        action: &Action,
+    ) -> Result<(), &'static str> {
+        let (from, index, to) = match action {
+            Action::Draw => {
+                let waste = table.cards(WASTE).unwrap();
+                return if table.deck.undealt_count() == 0 && waste.is_empty() {
+                    Err("Stock and waste are empty")
+                } else {
+                    Ok(())
+                };
+            }
+            Action::Move { from, index, to } => (*from, *index, *to),
+        };
+        if from == to {
+            return Err("Cards must move to another pile");
+        }
+        let moving = match table.area(from) {
+            Some(pile) if from != Zone::Hand(0) && index < pile.len() => &pile.cards()[index..],
+            _ => return Err("No such card"),
+        };
+        if index < self.face_down(from) {
+            return Err("Card is face down");
+        }
+        if !TABLEAU.contains(&from) && moving.len() > 1 {
+            return Err("Only the top card can be moved");
+        }
+        let target = table.cards(to).ok_or("No such pile")?;
+        if FOUNDATIONS.contains(&to) {
+            if moving.len() > 1 || !builds_on_foundation(target, &moving[0]) {
+                return Err("Card does not build on the foundation");
+            }
+        } else if TABLEAU.contains(&to) {
+            let run = moving.windows(2).all(|w| builds_on_tableau(&w[..1], &w[1]));
+            if !run || !builds_on_tableau(target, &moving[0]) {
+                return Err("Cards do not build on the tableau");
+            }
+        } else {
+            return Err("Cards cannot be moved there");
+        }
+        Ok(())
+    }
+
+    fn apply(
+        &mut self,
+        table: &mut Table,
+        _turn: &Turn<()>,
+        action: &Action,
+    ) -> Result<Transition<()>, &'static str> {
+        match action {
+            Action::Draw if table.deck.undealt_count() == 0 => {
+                // Turn the waste over so its bottom card is dealt first
+                let waste = table.area_mut(WASTE).unwrap();
+                let mut cards = core::mem::take(&mut waste.cards);
+                cards.reverse();
+                table.deck = Deck::from_cards(&cards);
+            }
+            Action::Draw => {
+                let _ = table.draw(WASTE)?;
+            }
+            Action::Move { from, index, to } => {
+                let moving = table.cards(*from).unwrap()[*index..].to_vec();
+                for card in &moving {
+                    table.move_card(*from, *to, card)?;
+                }
+                // Turn over the card that was uncovered
+                if let Some(pile) = TABLEAU.iter().position(|z| z == from) {
+                    let len = table.cards(*from).unwrap().len();
+                    if self.face_down[pile] >= len && len > 0 {
+                        self.face_down[pile] = len - 1;
+                    }
+                }
+            }
+        }
+        if Klondike::is_won(table) {
+            Ok(Transition::GameOver)
+        } else {
+            Ok(Transition::Stay)
+        }
+    }
+
+    fn is_public(&self, _turn: &Turn<()>, zone: Zone, index: usize) -> bool {
+        zone != Zone::Hand(0) && index >= self.face_down(zone)
+    }
+}
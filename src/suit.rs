@@ -1,10 +1,10 @@
-use std::cmp::Ordering;
-use std::slice::Iter;
+use core::cmp::Ordering;
+use core::slice::Iter;
 
 use self::Suit::*;
 
 /// This enumeration holds the suits in a standard deck of cards.
-#[derive(Copy, Clone, Hash, PartialEq, Eq, PartialOrd, Debug)]
+#[derive(Copy, Clone, Hash, PartialEq, Eq, Debug)]
 pub enum Suit {
     Spades,
     Hearts,
@@ -32,10 +32,16 @@ impl Ord for Suit {
     }
 }
 
+impl PartialOrd for Suit {
+    fn partial_cmp(&self, other: &Suit) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Suit {
     /// Returns an iterator through the standard suits
     pub fn iterator() -> Iter<'static, Suit> {
-        Suit::suits().into_iter()
+        Suit::suits().iter()
     }
 
     /// Returns an ordinal for the suit
@@ -0,0 +1,219 @@
+//! Draws cards as SVG images.
+//!
+//! Every card has its rank and suit in the top left corner and upside down in the bottom right.
+//! Number cards have the standard layout of suit pips, court cards have a large letter and
+//! jokers have a star. Hearts and diamonds are red and the other suits are black, following
+//! `Suit::to_color`. Each function returns a complete SVG document.
+
+use core::fmt::Write;
+
+use super::*;
+
+/// The width of a card in SVG units
+pub const CARD_WIDTH: f64 = 100.0;
+
+/// The height of a card in SVG units
+pub const CARD_HEIGHT: f64 = 140.0;
+
+/// The space between cards and around the edge of a hand or tableau
+pub const GAP: f64 = 10.0;
+
+/// Where the pips of each number card go, as fractions of the width and height, indexed by the
+/// number of pips
+const PIPS: [&[(f64, f64)]; 11] = [
+    &[],
+    &[(0.5, 0.5)],
+    &[(0.5, 0.2), (0.5, 0.8)],
+    &[(0.5, 0.2), (0.5, 0.5), (0.5, 0.8)],
+    &[(0.3, 0.2), (0.7, 0.2), (0.3, 0.8), (0.7, 0.8)],
+    &[(0.3, 0.2), (0.7, 0.2), (0.5, 0.5), (0.3, 0.8), (0.7, 0.8)],
+    &[
+        (0.3, 0.2),
+        (0.7, 0.2),
+        (0.3, 0.5),
+        (0.7, 0.5),
+        (0.3, 0.8),
+        (0.7, 0.8),
+    ],
+    &[
+        (0.3, 0.2),
+        (0.7, 0.2),
+        (0.5, 0.35),
+        (0.3, 0.5),
+        (0.7, 0.5),
+        (0.3, 0.8),
+        (0.7, 0.8),
+    ],
+    &[
+        (0.3, 0.2),
+        (0.7, 0.2),
+        (0.5, 0.35),
+        (0.3, 0.5),
+        (0.7, 0.5),
+        (0.5, 0.65),
+        (0.3, 0.8),
+        (0.7, 0.8),
+    ],
+    &[
+        (0.3, 0.2),
+        (0.7, 0.2),
+        (0.3, 0.4),
+        (0.7, 0.4),
+        (0.5, 0.5),
+        (0.3, 0.6),
+        (0.7, 0.6),
+        (0.3, 0.8),
+        (0.7, 0.8),
+    ],
+    &[
+        (0.3, 0.2),
+        (0.7, 0.2),
+        (0.5, 0.3),
+        (0.3, 0.4),
+        (0.7, 0.4),
+        (0.3, 0.6),
+        (0.7, 0.6),
+        (0.5, 0.7),
+        (0.3, 0.8),
+        (0.7, 0.8),
+    ],
+];
+
+/// Draws a card
+///
+/// # Example
+///
+/// ```
+/// use deckofcards::{card, svg};
+///
+/// let image = svg::card(&card!("7D"));
+/// assert!(image.starts_with("<svg"));
+/// assert_eq!(image.matches("class=\"pip\"").count(), 7);
+/// ```
+pub fn card(card: &Card) -> String {
+    document(CARD_WIDTH, CARD_HEIGHT, &card_group(card, 0.0, 0.0))
+}
+
+/// Draws a joker
+pub fn joker(joker: &Joker) -> String {
+    document(CARD_WIDTH, CARD_HEIGHT, &joker_group(joker, 0.0, 0.0))
+}
+
+/// Draws the cards of a hand side by side
+pub fn hand(hand: &Hand) -> String {
+    tableau(&[hand.cards()])
+}
+
+/// Draws rows of cards, one row under another, e.g. the piles of a game of patience
+pub fn tableau(rows: &[&[Card]]) -> String {
+    let columns = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let width = GAP + columns as f64 * (CARD_WIDTH + GAP);
+    let height = GAP + rows.len() as f64 * (CARD_HEIGHT + GAP);
+    let mut body = String::new();
+    for (r, row) in rows.iter().enumerate() {
+        let y = GAP + r as f64 * (CARD_HEIGHT + GAP);
+        for (c, card) in row.iter().enumerate() {
+            let x = GAP + c as f64 * (CARD_WIDTH + GAP);
+            body.push_str(&card_group(card, x, y));
+        }
+    }
+    document(width, height, &body)
+}
+
+fn document(width: f64, height: f64, body: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">{body}</svg>",
+        w = width,
+        h = height,
+        body = body
+    )
+}
+
+fn fill(color: Color) -> &'static str {
+    match color {
+        Color::Red => "#c8102e",
+        Color::Black => "#000000",
+    }
+}
+
+/// Draws a card at the position
+fn card_group(card: &Card, x: f64, y: f64) -> String {
+    let fill = fill(card.suit.to_color());
+    let rank = match card.rank {
+        Rank::Ten => "10".to_string(),
+        rank => rank.to_char().to_string(),
+    };
+    let suit = card.suit.to_unicode();
+
+    let mut face = String::new();
+    match card.rank {
+        Rank::Jack | Rank::Queen | Rank::King => {
+            let _ = write!(
+                face,
+                "<text x=\"50\" y=\"86\" font-size=\"56\" text-anchor=\"middle\" fill=\"{}\">{}</text>",
+                fill,
+                card.rank.to_char()
+            );
+            face.push_str(&pip(suit, 0.5, 0.25, 20.0));
+        }
+        rank => {
+            let count = match rank {
+                Rank::Ace => 1,
+                rank => rank.ordinal() + 2,
+            };
+            let size = if count == 1 { 48.0 } else { 22.0 };
+            for (px, py) in PIPS[count] {
+                face.push_str(&pip(suit, *px, *py, size));
+            }
+        }
+    }
+    group(x, y, fill, &rank, &suit.to_string(), &face)
+}
+
+/// Draws a joker at the position
+fn joker_group(joker: &Joker, x: f64, y: f64) -> String {
+    let fill = fill(joker.color);
+    let face = format!(
+        "{}<text x=\"50\" y=\"112\" font-size=\"14\" text-anchor=\"middle\" fill=\"{}\">JOKER</text>",
+        pip('★', 0.5, 0.45, 48.0),
+        fill
+    );
+    group(x, y, fill, "JK", "★", &face)
+}
+
+/// Draws the outline and corner indices of a card around the face
+fn group(x: f64, y: f64, fill: &str, rank: &str, suit: &str, face: &str) -> String {
+    let index = format!(
+        "<text x=\"10\" y=\"18\" font-size=\"14\" text-anchor=\"middle\">{}</text><text x=\"10\" y=\"32\" font-size=\"12\" text-anchor=\"middle\">{}</text>",
+        rank, suit
+    );
+    format!(
+        "<g transform=\"translate({x},{y})\" font-family=\"serif\" fill=\"{fill}\">\
+<rect x=\"0.5\" y=\"0.5\" width=\"{w}\" height=\"{h}\" rx=\"6\" fill=\"#ffffff\" stroke=\"#808080\"/>\
+{index}<g transform=\"rotate(180 {cx} {cy})\">{index}</g>{face}</g>",
+        x = x,
+        y = y,
+        fill = fill,
+        w = CARD_WIDTH - 1.0,
+        h = CARD_HEIGHT - 1.0,
+        cx = CARD_WIDTH / 2.0,
+        cy = CARD_HEIGHT / 2.0,
+        index = index,
+        face = face
+    )
+}
+
+/// Draws a suit symbol centered on the fractions of the card, upside down in the lower half
+fn pip(symbol: char, fx: f64, fy: f64, size: f64) -> String {
+    let x = fx * CARD_WIDTH;
+    let y = fy * CARD_HEIGHT;
+    let rotate = if fy > 0.5 {
+        format!(" transform=\"rotate(180 {} {})\"", x, y)
+    } else {
+        String::new()
+    };
+    format!(
+        "<text class=\"pip\" x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\"{}>{}</text>",
+        x, y, size, rotate, symbol
+    )
+}
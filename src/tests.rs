@@ -457,3 +457,1814 @@ fn hand_cards_of_rank() {
     let cards = h.cards_of_rank(Rank::King);
     assert_eq!(cards.len(), 0);
 }
+
+#[test]
+fn rummy_best_melds() {
+    use rummy::Meld;
+
+    // A set, an ace low run and deadwood of 2 + 10
+    let melding =
+        rummy::best_melds(&hand!("7S", "7H", "7D", "AC", "2C", "3C", "2H", "KD")).unwrap();
+    assert_eq!(melding.melds.len(), 2);
+    assert!(melding
+        .melds
+        .contains(&Meld::Set(vec![card!("7S"), card!("7H"), card!("7D")])));
+    assert!(melding
+        .melds
+        .contains(&Meld::Run(vec![card!("AC"), card!("2C"), card!("3C")])));
+    assert_eq!(melding.deadwood, vec![card!("2H"), card!("KD")]);
+    assert_eq!(melding.deadwood_value(), 12);
+
+    // Aces are low so Q-K-A is not a run
+    let melding = rummy::best_melds(&hand!("QS", "KS", "AS")).unwrap();
+    assert!(melding.melds.is_empty());
+    assert_eq!(melding.deadwood_value(), 21);
+
+    // The 8 of hearts is worth more in the run than the set of 8s, leaving the 8s as a set of 3
+    let melding =
+        rummy::best_melds(&hand!("8S", "8H", "8D", "8C", "6H", "7H", "9H", "4D")).unwrap();
+    assert_eq!(melding.deadwood, vec![card!("4D")]);
+    assert!(melding.melds.contains(&Meld::Run(vec![
+        card!("6H"),
+        card!("7H"),
+        card!("8H"),
+        card!("9H")
+    ])));
+
+    let melding = rummy::best_melds(&hand!(
+        "AS", "2S", "3S", "4S", "5D", "5H", "5C", "JC", "QC", "KC"
+    ))
+    .unwrap();
+    assert!(melding.is_gin());
+
+    // Too many cards to search is an error rather than a panic or a long wait
+    let mut deck = Deck::new();
+    assert!(rummy::best_melds(&Hand::from_cards(&deck.deal(17))).is_err());
+}
+
+#[test]
+fn rummy_lay_off() {
+    use rummy::Meld;
+
+    let melds = vec![
+        Meld::Run(vec![card!("5H"), card!("6H"), card!("7H")]),
+        Meld::Set(vec![card!("8S"), card!("8D"), card!("8C")]),
+    ];
+    // 8H fits either meld but going on the run lets the 9H follow it
+    let (melds, remaining) = rummy::lay_off(&melds, &[card!("9H"), card!("8H"), card!("KD")]);
+    assert_eq!(remaining, vec![card!("KD")]);
+    assert_eq!(melds[0].cards().len(), 5);
+    assert_eq!(melds[1].cards().len(), 3);
+
+    // Nothing can be laid off onto an empty meld
+    assert!(!Meld::Run(Vec::new()).accepts(&card!("9H")));
+    assert!(!Meld::Set(Vec::new()).accepts(&card!("9H")));
+}
+
+#[test]
+fn rummy_knock() {
+    use rummy::KnockOutcome;
+
+    let knocker = hand!("AS", "2S", "3S", "4S", "5D", "5H", "5C", "JC", "QC", "KC");
+    let opponent = hand!("9D", "9H", "9C", "2D", "3D", "6H", "6S", "TD", "TH", "KH");
+    let score = rummy::knock(&knocker, &opponent).unwrap();
+    assert_eq!(score.outcome, KnockOutcome::Gin);
+    assert_eq!(score.points, rummy::GIN_BONUS + 47);
+
+    // The opponent lays off the 4S and 8C onto the knocker's runs
+    let knocker = hand!("AS", "2S", "3S", "5D", "5H", "5C", "9C", "TC", "JC", "3H");
+    let opponent = hand!("9D", "9H", "9S", "KD", "KH", "KS", "4S", "2D", "6H", "8C");
+    let score = rummy::knock(&knocker, &opponent).unwrap();
+    assert_eq!(score.outcome, KnockOutcome::Knock);
+    assert_eq!(score.opponent.deadwood, vec![card!("6H"), card!("2D")]);
+    assert_eq!(score.points, 8 - 3);
+
+    let knocker = hand!("AS", "2S", "3S", "5D", "5H", "5C", "9C", "TC", "JC", "7H");
+    let opponent = hand!("9D", "9H", "9S", "KD", "KH", "KS", "4S", "2D", "AH", "8C");
+    let score = rummy::knock(&knocker, &opponent).unwrap();
+    assert_eq!(score.outcome, KnockOutcome::Undercut);
+    assert_eq!(score.points, rummy::UNDERCUT_BONUS + 7 - 3);
+
+    assert!(rummy::knock(&hand!("KS", "QD", "2C"), &opponent).is_err());
+}
+
+#[test]
+fn poker_evaluate() {
+    use poker::{evaluate, Category};
+
+    let value = |hand: Hand| evaluate(hand.cards()).unwrap();
+    let hands = [
+        (hand!("AS", "KD", "9C", "7H", "3S"), Category::HighCard),
+        (hand!("AS", "AD", "9C", "7H", "3S"), Category::OnePair),
+        (hand!("AS", "AD", "9C", "9H", "3S"), Category::TwoPair),
+        (hand!("9S", "AD", "9C", "9H", "3S"), Category::ThreeOfAKind),
+        (hand!("AS", "2D", "3C", "4H", "5S"), Category::Straight),
+        (hand!("TS", "JD", "QC", "KH", "AS"), Category::Straight),
+        (hand!("2H", "9H", "QH", "KH", "4H"), Category::Flush),
+        (hand!("9S", "3D", "9C", "9H", "3S"), Category::FullHouse),
+        (hand!("9S", "9D", "9C", "9H", "3S"), Category::FourOfAKind),
+        (hand!("5H", "4H", "3H", "2H", "AH"), Category::StraightFlush),
+    ];
+    for (hand, category) in hands.iter().cloned() {
+        assert_eq!(value(hand).category, category);
+    }
+
+    // The wheel is the lowest straight and the ace is its last card
+    let wheel = value(hand!("AS", "2D", "3C", "4H", "5S"));
+    assert_eq!(wheel.ranks, vec![Rank::Five]);
+    assert_eq!(wheel.cards.last(), Some(&card!("AS")));
+    assert!(wheel < value(hand!("2S", "3D", "4C", "5H", "6S")));
+
+    // Kickers break ties and suits do not
+    assert!(
+        value(hand!("AS", "AD", "KC", "7H", "3S")) > value(hand!("AH", "AC", "QC", "7H", "3S"))
+    );
+    assert_eq!(
+        value(hand!("AS", "AD", "KC", "7H", "3S")),
+        value(hand!("AH", "AC", "KD", "7S", "3H"))
+    );
+
+    // The best five of seven cards
+    let best = value(hand!("2C", "9S", "9D", "KH", "KC", "9H", "4D"));
+    assert_eq!(best.category, Category::FullHouse);
+    assert_eq!(best.ranks, vec![Rank::Nine, Rank::King]);
+
+    assert!(evaluate(hand!("AS", "KS", "QS", "JS").cards()).is_err());
+    assert!(evaluate(hand!("AS", "KS", "QS", "JS", "AS").cards()).is_err());
+}
+
+/// A game where each turn a player draws from the stock or discard pile and then discards
+struct DrawDiscard;
+
+#[derive(Clone, Debug, PartialEq)]
+enum DrawDiscardPhase {
+    Draw,
+    Discard,
+}
+
+#[derive(Clone, Debug)]
+enum DrawDiscardAction {
+    DrawStock,
+    DrawDiscard,
+    Discard(Card),
+}
+
+impl game::Rules for DrawDiscard {
+    type Card = Card;
+    type Hand = Hand;
+    type Phase = DrawDiscardPhase;
+    type Action = DrawDiscardAction;
+
+    fn initial_phase(&self) -> DrawDiscardPhase {
+        DrawDiscardPhase::Draw
+    }
+
+    fn validate(
+        &self,
+        table: &game::Table,
+        turn: &game::Turn<DrawDiscardPhase>,
+        action: &DrawDiscardAction,
+    ) -> Result<(), &'static str> {
+        match (&turn.phase, action) {
+            (DrawDiscardPhase::Draw, DrawDiscardAction::DrawStock) => Ok(()),
+            (DrawDiscardPhase::Draw, DrawDiscardAction::DrawDiscard) => {
+                match table.cards(game::Zone::Discard) {
+                    Some(cards) if !cards.is_empty() => Ok(()),
+                    _ => Err("Discard pile is empty"),
+                }
+            }
+            (DrawDiscardPhase::Discard, DrawDiscardAction::Discard(card)) => {
+                if table.seats[turn.seat].hand.cards().contains(card) {
+                    Ok(())
+                } else {
+                    Err("Card is not in hand")
+                }
+            }
+            _ => Err("Action not allowed in this phase"),
+        }
+    }
+
+    fn apply(
+        &mut self,
+        table: &mut game::Table,
+        turn: &game::Turn<DrawDiscardPhase>,
+        action: &DrawDiscardAction,
+    ) -> Result<game::Transition<DrawDiscardPhase>, &'static str> {
+        let hand = game::Zone::Hand(turn.seat);
+        match action {
+            DrawDiscardAction::DrawStock => {
+                let _ = table.draw(hand)?;
+            }
+            DrawDiscardAction::DrawDiscard => {
+                let card = *table.cards(game::Zone::Discard).unwrap().last().unwrap();
+                table.move_card(game::Zone::Discard, hand, &card)?;
+            }
+            DrawDiscardAction::Discard(card) => {
+                table.move_card(hand, game::Zone::Discard, card)?;
+                if table.deck.undealt_count() == 0 {
+                    return Ok(game::Transition::GameOver);
+                }
+                return Ok(game::Transition::NextSeat(DrawDiscardPhase::Draw));
+            }
+        }
+        Ok(game::Transition::Phase(DrawDiscardPhase::Discard))
+    }
+}
+
+#[test]
+fn game_table_deal_and_move() {
+    let mut table: game::Table = game::Table::new(deck!(), &["North", "South"]);
+    assert_eq!(table.deal(5), 10);
+    assert_eq!(table.seats[0].hand.len(), 5);
+    assert_eq!(table.seats[1].hand.len(), 5);
+    assert_eq!(table.deck.undealt_count(), 42);
+
+    let card = table.seats[0].hand.cards()[0];
+    assert!(table
+        .move_card(game::Zone::Hand(0), game::Zone::Board, &card)
+        .is_ok());
+    assert_eq!(table.cards(game::Zone::Board).unwrap(), &[card]);
+    assert!(table
+        .move_card(game::Zone::Hand(0), game::Zone::Board, &card)
+        .is_err());
+    assert!(table
+        .move_card(game::Zone::Board, game::Zone::Area(0), &card)
+        .is_err());
+    let crib = table.add_area("Crib");
+    assert_eq!(crib, game::Zone::Area(0));
+    assert_eq!(table.add_area("Crib"), crib);
+    assert!(table.move_card(game::Zone::Board, crib, &card).is_ok());
+
+    // Only the top card can leave the stock, and cards returned to it go underneath
+    let top = table.deck.top_card().unwrap();
+    let bottom = table.deck.bottom_card().unwrap();
+    assert!(table
+        .move_card(game::Zone::Stock, game::Zone::Discard, &bottom)
+        .is_err());
+    assert!(table
+        .move_card(game::Zone::Stock, game::Zone::Discard, &top)
+        .is_ok());
+    assert!(table
+        .move_card(game::Zone::Discard, game::Zone::Stock, &top)
+        .is_ok());
+    assert_eq!(table.deck.bottom_card(), Some(top));
+}
+
+#[test]
+fn game_turns_and_phases() {
+    let mut table: game::Table = game::Table::new(
+        Deck::from_cards(&[card!("2C"), card!("3C"), card!("4C"), card!("5C")]),
+        &["A", "B"],
+    );
+    assert_eq!(table.deal(1), 2);
+    let mut game = game::Game::new(table, DrawDiscard).unwrap();
+
+    assert_eq!(game.turn().seat, 0);
+    assert_eq!(game.turn().phase, DrawDiscardPhase::Draw);
+    assert!(game.act(1, &DrawDiscardAction::DrawStock).is_err());
+    assert!(game.act(0, &DrawDiscardAction::DrawDiscard).is_err());
+    assert!(game
+        .act(0, &DrawDiscardAction::Discard(card!("5C")))
+        .is_err());
+
+    assert!(game.act(0, &DrawDiscardAction::DrawStock).is_ok());
+    assert_eq!(game.turn().phase, DrawDiscardPhase::Discard);
+    assert!(game
+        .act(0, &DrawDiscardAction::Discard(card!("AS")))
+        .is_err());
+    assert!(game
+        .act(0, &DrawDiscardAction::Discard(card!("5C")))
+        .is_ok());
+    assert_eq!(game.turn().seat, 1);
+    assert_eq!(game.turn().phase, DrawDiscardPhase::Draw);
+
+    assert!(game.act(1, &DrawDiscardAction::DrawDiscard).is_ok());
+    assert!(game.table.seats[1].hand.cards().contains(&card!("5C")));
+    assert!(game
+        .act(1, &DrawDiscardAction::Discard(card!("4C")))
+        .is_ok());
+    assert_eq!(game.turn().seat, 0);
+
+    assert!(game.act(0, &DrawDiscardAction::DrawStock).is_ok());
+    assert!(game
+        .act(0, &DrawDiscardAction::Discard(card!("2C")))
+        .is_ok());
+    assert!(game.is_over());
+    assert!(game.act(1, &DrawDiscardAction::DrawStock).is_err());
+
+    let table: game::Table = game::Table::new(Deck::new(), &[]);
+    assert!(game::Game::new(table, DrawDiscard).is_err());
+}
+
+#[cfg(feature = "games")]
+#[test]
+fn solitaire_klondike() {
+    use game::Zone;
+    use solitaire::{Action, Klondike, FOUNDATIONS, TABLEAU, WASTE};
+
+    let mut game = Klondike::deal(deck!());
+    for (i, pile) in TABLEAU.iter().enumerate() {
+        assert_eq!(game.table.cards(*pile).unwrap().len(), i + 1);
+        assert_eq!(game.rules.face_down(*pile), i);
+    }
+    assert_eq!(game.table.deck.undealt_count(), 24);
+
+    let mut set = |zone: Zone, cards: Vec<Card>| game.table.area_mut(zone).unwrap().cards = cards;
+    set(TABLEAU[0], vec![card!("5D"), card!("KS"), card!("QH")]);
+    set(TABLEAU[1], vec![]);
+    set(TABLEAU[2], vec![card!("JC")]);
+    set(WASTE, vec![card!("AH")]);
+    game.rules.face_down = [1, 0, 0, 0, 0, 0, 0];
+    let move_cards = |from, index, to| Action::Move { from, index, to };
+
+    assert!(game.act(0, &move_cards(WASTE, 0, TABLEAU[2])).is_err());
+    assert!(game.act(0, &move_cards(WASTE, 0, FOUNDATIONS[0])).is_ok());
+    assert!(game.act(0, &move_cards(TABLEAU[0], 0, TABLEAU[1])).is_err());
+    assert!(game.act(0, &move_cards(TABLEAU[2], 0, TABLEAU[1])).is_err());
+    assert!(game.act(0, &move_cards(TABLEAU[0], 1, TABLEAU[1])).is_ok());
+    assert_eq!(game.table.cards(TABLEAU[0]).unwrap(), &[card!("5D")]);
+    assert_eq!(game.rules.face_down(TABLEAU[0]), 0);
+    assert!(game.act(0, &move_cards(TABLEAU[2], 0, TABLEAU[1])).is_ok());
+    assert_eq!(game.table.cards(TABLEAU[1]).unwrap().len(), 3);
+
+    // Turning the stock onto the waste and the waste back over
+    game.table.deck = Deck::from_cards(&[card!("2C"), card!("3C")]);
+    assert!(game.act(0, &Action::Draw).is_ok());
+    assert!(game.act(0, &Action::Draw).is_ok());
+    assert_eq!(
+        game.table.cards(WASTE).unwrap(),
+        &[card!("3C"), card!("2C")]
+    );
+    assert!(game.act(0, &Action::Draw).is_ok());
+    assert_eq!(game.table.cards(WASTE).unwrap().len(), 0);
+    assert_eq!(game.table.deck.top_card(), Some(card!("3C")));
+}
+
+#[cfg(feature = "games")]
+#[test]
+fn blackjack_rounds() {
+    use blackjack::{Action, Blackjack, Outcome, Phase, DEALER, PLAYER};
+
+    let mut game = (0..)
+        .map(Blackjack::deal)
+        .find(|g| g.turn().phase == Phase::Playing)
+        .unwrap();
+    assert_eq!(game.table.seats[PLAYER].hand.len(), 2);
+    assert_eq!(game.table.seats[DEALER].hand.len(), 2);
+    assert!(game.act(PLAYER, &Action::Deal).is_err());
+
+    // The dealer draws to 17 or more
+    let arrange =
+        |game: &mut game::Game<Blackjack>, player: &[Card], dealer: &[Card], top: Card| {
+            let held = [player, dealer, &[top]].concat();
+            let mut rest = Card::all_cards()
+                .iter()
+                .filter(|c| !held.contains(c))
+                .copied()
+                .collect::<Vec<_>>();
+            rest.push(top);
+            game.table.deck = Deck::from_cards(&rest);
+            game.table.seats[PLAYER].hand.cards = player.to_vec();
+            game.table.seats[DEALER].hand.cards = dealer.to_vec();
+            game.table.area_mut(game::Zone::Discard).unwrap().clear();
+        };
+    arrange(
+        &mut game,
+        &[card!("TS"), card!("9D")],
+        &[card!("TC"), card!("6H")],
+        card!("5S"),
+    );
+    assert!(game.act(PLAYER, &Action::Stand).is_ok());
+    assert_eq!(game.table.seats[DEALER].hand.len(), 3);
+    assert_eq!(game.rules.outcome, Some(Outcome::Lose));
+    assert_eq!(game.rules.losses, 1);
+    assert_eq!(game.turn().phase, Phase::RoundOver);
+    assert!(game.act(PLAYER, &Action::Hit).is_err());
+
+    // Going over 21 loses straight away
+    while game.turn().phase != Phase::Playing {
+        assert!(game.act(PLAYER, &Action::Deal).is_ok());
+    }
+    arrange(
+        &mut game,
+        &[card!("TS"), card!("5D")],
+        &[card!("TC"), card!("6H")],
+        card!("KC"),
+    );
+    game.rules.outcome = None;
+    assert!(game.act(PLAYER, &Action::Hit).is_ok());
+    assert_eq!(game.rules.outcome, Some(Outcome::Lose));
+    assert_eq!(game.table.seats[DEALER].hand.len(), 2);
+
+    // The discards are shuffled back into the shoe when it runs low and no card is lost
+    for _ in 0..40 {
+        if game.turn().phase == Phase::Playing {
+            assert!(game.act(PLAYER, &Action::Stand).is_ok());
+        }
+        assert!(game.act(PLAYER, &Action::Deal).is_ok());
+        let held = game.table.seats.iter().map(|s| s.hand.len()).sum::<usize>();
+        let discards = game.table.cards(game::Zone::Discard).unwrap().len();
+        assert_eq!(held + discards + game.table.deck.undealt_count(), 52);
+        assert!(game.table.deck.undealt_count() + 4 >= blackjack::RESHUFFLE_AT);
+    }
+}
+
+#[cfg(feature = "games")]
+#[test]
+fn hearts_game() {
+    use hearts::{Action, Hearts, Phase};
+
+    let mut game = Hearts::deal(3);
+    let leader = game.turn().seat;
+    let next = (leader + 1) % hearts::SEATS;
+    let card = game.table.seats[next].hand.cards()[0];
+    assert!(game.act(next, &Action::Play(card)).is_err());
+    assert_eq!(
+        game.rules.legal_plays(&game.table, leader),
+        vec![card!("2C")]
+    );
+
+    // A copy of the game can be played without changing the original
+    let mut copy = game.clone();
+    assert!(copy.act(leader, &Action::Play(card!("2C"))).is_ok());
+    assert_eq!(copy.rules.trick.len(), 1);
+    assert!(game.rules.trick.is_empty());
+
+    // Play every game out with the first legal card, checking each hand scores 26 points, or 78
+    // when the moon is shot
+    let mut total = 0;
+    while !game.is_over() {
+        let seat = game.turn().seat;
+        if game.turn().phase == Phase::HandOver {
+            assert!(game.act(seat, &Action::Deal).is_ok());
+            assert!(game.rules.voids.iter().all(|v| v.is_empty()));
+            continue;
+        }
+        let plays = game.rules.legal_plays(&game.table, seat);
+        let hand = game.table.seats[seat].hand.cards();
+        let mut void = None;
+        if let Some((_, led)) = game.rules.trick.first() {
+            if hand.iter().any(|c| c.suit == led.suit) {
+                assert!(plays.iter().all(|c| c.suit == led.suit));
+            } else {
+                void = Some(led.suit);
+            }
+        }
+        assert!(game.act(seat, &Action::Play(plays[0])).is_ok());
+        if let Some(suit) = void.filter(|_| game.turn().phase == Phase::Play) {
+            assert!(game.rules.voids[seat].contains(&suit));
+        }
+        let scored = game.rules.scores.iter().sum::<usize>();
+        if scored != total {
+            assert!(scored - total == 26 || scored - total == 78);
+            total = scored;
+        }
+    }
+    assert!(game.rules.scores.iter().any(|s| *s >= hearts::GAME_OVER_AT));
+}
+
+#[test]
+fn history_deck_undo_redo() {
+    let mut deck = history::History::new(Deck::from_cards(&[
+        card!("AH"),
+        card!("2C"),
+        card!("3D"),
+        card!("4S"),
+    ]));
+    assert!(!deck.can_undo());
+    assert_eq!(deck.deal(2), vec![card!("4S"), card!("3D")]);
+    deck.seeded_shuffle(7);
+    deck.push(&[card!("KD")]);
+    deck.reset();
+    assert_eq!(deck.events().len(), 5);
+    let after = deck.cards().to_vec();
+
+    // Undo everything back to the initial state
+    while deck.undo() {}
+    assert_eq!(deck.cards(), deck.initial().cards());
+    assert_eq!(deck.dealt_count(), 0);
+
+    // Redo everything forward again
+    while deck.redo() {}
+    assert_eq!(deck.cards(), after.as_slice());
+    assert_eq!(deck.replay().cards(), after.as_slice());
+
+    // A new change after undoing discards the redo stack
+    assert!(deck.undo());
+    assert_eq!(deck.dealt_count(), 2);
+    assert!(deck.deal_one().is_ok());
+    assert!(!deck.can_redo());
+    assert_eq!(deck.dealt_count(), 3);
+}
+
+#[test]
+fn history_hand_undo_redo() {
+    let mut hand = history::History::new(hand!("QD", "KS", "3C", "KS"));
+    hand.push_card(card!("AS"));
+    hand.remove_all_cards(&[card!("KS")]);
+    assert_eq!(hand.cards(), hand!("QD", "3C", "AS").cards());
+    assert!(!hand.remove_card(&card!("2H")));
+    assert_eq!(hand.remove(0), card!("QD"));
+    hand.reorder(|cards| cards.sort());
+    assert_eq!(hand.cards(), hand!("3C", "AS").cards());
+    hand.clear();
+    assert!(hand.is_empty());
+
+    assert!(hand.undo());
+    assert!(hand.undo());
+    assert_eq!(hand.cards(), hand!("3C", "AS").cards());
+    assert!(hand.undo());
+    assert!(hand.undo());
+    assert_eq!(hand.cards(), hand!("QD", "KS", "3C", "KS", "AS").cards());
+    assert!(hand.undo());
+    assert_eq!(hand.cards(), hand!("QD", "KS", "3C", "KS").cards());
+    assert!(!hand.undo());
+
+    assert!(hand.redo());
+    assert!(hand.redo());
+    assert_eq!(hand.cards(), hand!("QD", "3C", "AS").cards());
+    assert_eq!(hand.replay().cards(), hand.cards());
+}
+
+#[test]
+fn hand_history_record_and_replay() {
+    use hand_history::{HandHistory, Origin, Recorder};
+
+    let mut recorder = Recorder::new(Origin::Seed(42), &["North", "South"]);
+    let crib = recorder.add_area("Crib");
+    assert_eq!(recorder.deal(3), 6);
+    let card = recorder.table().seats[0].hand.cards()[0];
+    assert!(recorder.move_card(game::Zone::Hand(0), crib, &card).is_ok());
+    assert!(recorder
+        .move_card(game::Zone::Hand(0), crib, &card)
+        .is_err());
+    assert!(recorder.bet(0, 10).is_ok());
+    assert!(recorder.bet(1, 10).is_ok());
+    assert!(recorder.win(1, 20).is_ok());
+    assert!(recorder.win(2, 20).is_err());
+
+    let expected = recorder.table().clone();
+    let history = recorder.finish();
+    assert_eq!(history.entries.len(), 10);
+    assert_eq!(history.pot(), 20);
+
+    // Write out, read back and replay to the same table
+    let text = history.to_string();
+    assert!(text.starts_with("seed 42\nseat North\nseat South\narea Crib\ndeal 0 "));
+    let read = text.parse::<HandHistory>().unwrap();
+    assert_eq!(read, history);
+    let table = read.replay().unwrap();
+    assert_eq!(table.seats[0].hand.cards(), expected.seats[0].hand.cards());
+    assert_eq!(table.seats[1].hand.cards(), expected.seats[1].hand.cards());
+    assert_eq!(table.cards(crib), Some(&[card][..]));
+    assert_eq!(table.deck.cards(), expected.deck.cards());
+
+    // Names keep their line breaks, backslashes and spaces at the ends
+    let names = [" Ann\nLee ", "C:\\Bob\t", "", "North Pole"];
+    let history = HandHistory::new(Origin::Seed(1), &names);
+    let text = history.to_string();
+    assert_eq!(text.lines().count(), 5);
+    assert!(text.contains("seat \\sAnn\\nLee\\s\n"));
+    assert_eq!(text.parse::<HandHistory>().unwrap(), history);
+    assert!("seed 1\nseat A\\x\n".parse::<HandHistory>().is_err());
+}
+
+#[test]
+fn hand_history_inconsistent() {
+    use hand_history::HandHistory;
+
+    let history = "deck 2C 3C 4C\nseat A\nseat B\ndeal 0 4C\ndeal 1 3C\n"
+        .parse::<HandHistory>()
+        .unwrap();
+    assert!(history.replay().is_ok());
+
+    // Cards must be dealt in deck order
+    let history = "deck 2C 3C 4C\nseat A\ndeal 0 3C\n"
+        .parse::<HandHistory>()
+        .unwrap();
+    assert!(history.replay().is_err());
+
+    // Cards can only be moved from where they are
+    let history = "deck 2C 3C 4C\nseat A\ndeal 0 4C\nmove hand:0 board 3C\n"
+        .parse::<HandHistory>()
+        .unwrap();
+    assert!(history.replay().is_err());
+
+    // No more can be won than was bet
+    let history = "# comment\nseed 1\nseat A\n\nbet 0 5\nwin 0 6\n"
+        .parse::<HandHistory>()
+        .unwrap();
+    assert!(history.replay().is_err());
+
+    // Amounts too large to add up are an error rather than an overflow
+    let history = "seed 1\nseat A\nbet 0 18446744073709551615\nbet 0 1\nwin 0 1\n"
+        .parse::<HandHistory>()
+        .unwrap();
+    assert_eq!(history.pot(), u64::MAX);
+    assert!(history.replay().is_err());
+
+    assert!("seat A\n".parse::<HandHistory>().is_err());
+    assert!("seed 1\nmove hand:0 nowhere AS\n"
+        .parse::<HandHistory>()
+        .is_err());
+    assert!("seed 1\ndeal x AS\n".parse::<HandHistory>().is_err());
+}
+
+#[test]
+fn pokerstars_parse() {
+    use pokerstars::{ActionKind, Site, Street};
+
+    let text = "\u{feff}PokerStars Hand #123456789: Hold'em No Limit ($0.01/$0.02 USD) - 2020/01/01 12:00:00 ET
+Table 'Alpha II' 6-max Seat #1 is the button
+Seat 1: Alice Smith ($2.00 in chips)
+Seat 2: Bob ($1.50 in chips)
+Seat 3: Carol ($2.10 in chips) is sitting out
+Alice Smith: posts small blind $0.01
+Bob: posts big blind $0.02
+*** HOLE CARDS ***
+Dealt to Alice Smith [Ah Kd]
+Alice Smith: raises $0.04 to $0.06
+Bob: calls $0.04
+*** FLOP *** [2c 7h Ts]
+Alice Smith: bets $0.10
+Bob: raises $0.10 to $0.20
+Alice Smith: calls $0.10
+*** TURN *** [2c 7h Ts] [Jd]
+Bob: bets $1.24 and is all-in
+Alice Smith: calls $1.24
+*** RIVER *** [2c 7h Ts Jd] [3s]
+*** SHOW DOWN ***
+Bob: shows [Qs Qh] (a pair of Queens)
+Alice Smith: shows [Ah Kd] (high card Ace)
+Bob collected $2.97 from pot
+*** SUMMARY ***
+Total pot $3 | Rake $0.03
+Board [2c 7h Ts Jd 3s]
+Seat 1: Alice Smith (button) (small blind) showed [Ah Kd] and lost with high card Ace
+Seat 2: Bob (big blind) showed [Qs Qh] and won ($2.97) with a pair of Queens
+
+PokerStars Hand #123456790: Hold'em No Limit ($0.01/$0.02 USD) - 2020/01/01 12:01:00 ET
+Table 'Alpha II' 6-max Seat #2 is the button
+Seat 1: Alice Smith ($0.97 in chips)
+Seat 2: Bob ($2.53 in chips)
+Bob: posts small blind $0.01
+Alice Smith: posts big blind $0.02
+*** HOLE CARDS ***
+Dealt to Alice Smith [9c 9d]
+Bob: folds
+Uncalled bet ($0.01) returned to Alice Smith
+Alice Smith collected $0.02 from pot
+*** SUMMARY ***
+Total pot $0.02 | Rake $0
+";
+    let hands = pokerstars::parse(text).unwrap();
+    assert_eq!(hands.len(), 2);
+
+    let hand = &hands[0];
+    assert_eq!(hand.site, Site::PokerStars);
+    assert_eq!(hand.id, "123456789");
+    assert_eq!(hand.game, "Hold'em No Limit ($0.01/$0.02 USD)");
+    assert_eq!(hand.table, "Alpha II");
+    assert_eq!(hand.button, Some(1));
+    assert_eq!(hand.seats.len(), 3);
+    assert_eq!(hand.seats[0].name, "Alice Smith");
+    assert_eq!(hand.seats[0].stack, 200);
+    assert_eq!(hand.seats[2].name, "Carol");
+    assert_eq!(
+        hand.hole_cards_of("Alice Smith"),
+        Some(&[card!("AH"), card!("KD")][..])
+    );
+    assert_eq!(
+        hand.hole_cards_of("Bob"),
+        Some(&[card!("QS"), card!("QH")][..])
+    );
+    assert_eq!(
+        hand.board,
+        vec![
+            card!("2C"),
+            card!("7H"),
+            card!("TS"),
+            card!("JD"),
+            card!("3S")
+        ]
+    );
+    assert_eq!(hand.actions[0].kind, ActionKind::SmallBlind(1));
+    assert_eq!(hand.actions[2].kind, ActionKind::Raise(4, 6));
+    assert_eq!(hand.actions[2].street, Street::Preflop);
+    assert_eq!(hand.actions[5].kind, ActionKind::Raise(10, 20));
+    assert_eq!(hand.actions[5].street, Street::Flop);
+    assert!(hand.actions[7].all_in);
+    assert_eq!(hand.actions[7].kind, ActionKind::Bet(124));
+    assert_eq!(hand.actions[9].street, Street::Showdown);
+    assert_eq!(hand.collected[0].player, "Bob");
+    assert_eq!(hand.collected[0].amount, 297);
+    assert_eq!(hand.total_pot, Some(300));
+    assert_eq!(hand.rake, Some(3));
+
+    let hand = &hands[1];
+    assert_eq!(hand.button, Some(2));
+    assert!(hand.board.is_empty());
+    assert_eq!(hand.actions[3].kind, ActionKind::Return(1));
+    assert_eq!(hand.actions[3].player, "Alice Smith");
+}
+
+#[test]
+fn pokerstars_parse_full_tilt() {
+    use pokerstars::{ActionKind, PokerHand, Site};
+
+    let text = "Full Tilt Poker Game #12345: Table Alpha (6 max) - $0.01/$0.02 - No Limit Hold'em - 12:00:00 ET - 2010/01/01
+Seat 1: Alice ($2)
+Seat 2: Bob ($1.50)
+Alice posts the small blind of $0.01
+Bob posts the big blind of $0.02
+The button is in seat #1
+*** HOLE CARDS ***
+Dealt to Alice [Ah Kd]
+Alice raises to $0.06
+Bob calls $0.04
+*** FLOP *** [2c 7h Ts]
+Alice bets $0.10
+Bob folds
+Uncalled bet of $0.10 returned to Alice
+Alice wins the pot ($0.12)
+*** SUMMARY ***
+Total pot $0.12 | Rake $0
+Board: [2c 7h Ts]
+";
+    let hand = text.parse::<PokerHand>().unwrap();
+    assert_eq!(hand.site, Site::FullTilt);
+    assert_eq!(hand.id, "12345");
+    assert_eq!(hand.table, "Alpha");
+    assert_eq!(hand.game, "$0.01/$0.02 - No Limit Hold'em");
+    assert_eq!(hand.button, Some(1));
+    assert_eq!(hand.seats[1].stack, 150);
+    assert_eq!(hand.actions[1].kind, ActionKind::BigBlind(2));
+    assert_eq!(hand.actions[2].kind, ActionKind::Raise(4, 6));
+    assert_eq!(hand.actions[5].kind, ActionKind::Fold);
+    assert_eq!(hand.actions[6].kind, ActionKind::Return(10));
+    assert_eq!(hand.collected[0].amount, 12);
+    assert_eq!(hand.board.len(), 3);
+
+    // A re-raise is worked out from the total of the raise before it
+    let reraise = "Full Tilt Poker Game #12346: Table Alpha (6 max) - $0.05/$0.10 - No Limit Hold'em - 12:00:00 ET - 2010/01/01
+Seat 1: Alice ($10)
+Seat 2: Bob ($10)
+Seat 3: Carol ($10)
+Alice posts the small blind of $0.05
+Bob posts the big blind of $0.10
+*** HOLE CARDS ***
+Carol raises to $0.30
+Alice raises to $0.90
+Bob folds
+Carol raises to $2.70
+Alice folds
+Uncalled bet of $1.80 returned to Carol
+Carol wins the pot ($1.90)
+";
+    let hand = reraise.parse::<PokerHand>().unwrap();
+    assert_eq!(hand.actions[2].kind, ActionKind::Raise(20, 30));
+    assert_eq!(hand.actions[3].kind, ActionKind::Raise(60, 90));
+    assert_eq!(hand.actions[5].kind, ActionKind::Raise(180, 270));
+
+    let error = pokerstars::parse("Seat 1: Alice ($2)\n").unwrap_err();
+    assert_eq!(error.line, 1);
+    let error = pokerstars::parse(&text.replace("[Ah Kd]", "[Ah Zd]")).unwrap_err();
+    assert_eq!(error.line, 8);
+    let error = pokerstars::parse(&text.replace("($1.50)", "($999999999999999999)")).unwrap_err();
+    assert_eq!(error.line, 3);
+}
+
+#[test]
+fn notation_lenient() {
+    use notation::{parse_card, Strictness::Lenient};
+
+    let ten_of_hearts = Card::new(Rank::Ten, Suit::Hearts);
+    assert_eq!(parse_card("TH", Lenient), Ok(ten_of_hearts));
+    assert_eq!(parse_card("th", Lenient), Ok(ten_of_hearts));
+    assert_eq!(parse_card("10h", Lenient), Ok(ten_of_hearts));
+    assert_eq!(parse_card("10♥", Lenient), Ok(ten_of_hearts));
+    assert_eq!(parse_card("10♡", Lenient), Ok(ten_of_hearts));
+    assert_eq!(parse_card("h10", Lenient), Ok(ten_of_hearts));
+    assert_eq!(parse_card("♥T", Lenient), Ok(ten_of_hearts));
+    assert_eq!(parse_card("  ten of hearts ", Lenient), Ok(ten_of_hearts));
+    assert_eq!(parse_card("10 of Heart", Lenient), Ok(ten_of_hearts));
+    assert_eq!(parse_card("🂺", Lenient), Ok(ten_of_hearts));
+    assert_eq!(parse_card("as", Lenient), Ok(card!("AS")));
+    assert_eq!(parse_card("1s", Lenient), Ok(card!("AS")));
+    assert_eq!(parse_card("ct", Lenient), Ok(card!("TC")));
+    assert_eq!(parse_card("🂡", Lenient), Ok(card!("AS")));
+    assert_eq!(parse_card("🃞", Lenient), Ok(card!("KC")));
+    assert_eq!(parse_card("🃍", Lenient), Ok(card!("QD")));
+
+    // Every card round trips through its short form, pretty form and name
+    for card in Card::iterator() {
+        assert_eq!(parse_card(&card.to_str(), Lenient), Ok(*card));
+        assert_eq!(parse_card(&card.name(), Lenient), Ok(*card));
+        let pretty = format!("{}{}", card.rank.to_char(), card.suit.to_unicode());
+        assert_eq!(parse_card(&pretty, Lenient), Ok(*card));
+    }
+}
+
+#[test]
+fn notation_strict_and_errors() {
+    use notation::{parse_card, ParseError, Strictness::Lenient, Strictness::Strict};
+
+    assert_eq!(parse_card("AS", Strict), Ok(card!("AS")));
+    assert_eq!(parse_card("A♠", Strict), Ok(card!("AS")));
+    assert_eq!(parse_card("Ace of Spades", Strict), Ok(card!("AS")));
+    assert!(parse_card("as", Strict).is_err());
+    assert!(parse_card("10S", Strict).is_err());
+    assert!(parse_card("SA", Strict).is_err());
+    assert!(parse_card(" AS", Strict).is_err());
+    assert!(parse_card("ace of spades", Strict).is_err());
+    // The knight is not in a standard deck
+    assert!(parse_card("🂬", Strict).is_err());
+
+    let error = |position, reason| Err(ParseError { position, reason });
+    assert_eq!(parse_card("", Lenient), error(0, "Empty string"));
+    assert_eq!(parse_card("ZS", Lenient), error(0, "Invalid rank"));
+    assert_eq!(parse_card("AX", Lenient), error(1, "Invalid suit"));
+    assert_eq!(parse_card("  10x", Lenient), error(4, "Invalid suit"));
+    assert_eq!(
+        parse_card("10hh", Lenient),
+        error(3, "Unexpected characters")
+    );
+    assert_eq!(parse_card("A", Lenient), error(1, "Missing suit"));
+    assert_eq!(
+        parse_card("Ace of Spadez", Lenient),
+        error(7, "Invalid suit")
+    );
+    assert_eq!(
+        parse_card("Ace in Spades", Lenient),
+        error(4, "Expected \"of\"")
+    );
+    assert_eq!(
+        parse_card("Eleven of Spades", Lenient),
+        error(0, "Invalid rank")
+    );
+    assert_eq!(
+        parse_card("Ace of Spades please", Lenient),
+        error(14, "Unexpected characters")
+    );
+    assert_eq!(
+        parse_card("ZS", Lenient).unwrap_err().to_string(),
+        "Invalid rank at position 0"
+    );
+}
+
+#[test]
+fn range_parse() {
+    use range::{Combo, Range};
+
+    let range = "TT+".parse::<Range>().unwrap();
+    assert_eq!(range.len(), 5 * 6);
+    assert!(range.contains(&Combo::new(card!("AS"), card!("AD"))));
+    assert!(!range.contains(&Combo::new(card!("9S"), card!("9D"))));
+
+    assert_eq!("AKs".parse::<Range>().unwrap().len(), 4);
+    assert_eq!("AKo".parse::<Range>().unwrap().len(), 12);
+    assert_eq!("AK".parse::<Range>().unwrap().len(), 16);
+    assert_eq!("A2s+".parse::<Range>().unwrap().len(), 12 * 4);
+    assert_eq!("KTo+".parse::<Range>().unwrap().len(), 3 * 12);
+    assert_eq!("A2s-A5s".parse::<Range>().unwrap().len(), 4 * 4);
+    assert_eq!("77-TT".parse::<Range>().unwrap().len(), 4 * 6);
+    assert_eq!("TT-77".parse::<Range>().unwrap().len(), 4 * 6);
+
+    let range = "AhKh, QQ:0.5,  AKs:0.25".parse::<Range>().unwrap();
+    assert_eq!(range.len(), 6 + 4);
+    assert_eq!(range.weight(&Combo::new(card!("KH"), card!("AH"))), 0.25);
+    assert_eq!(range.weight(&Combo::new(card!("QH"), card!("QC"))), 0.5);
+    assert_eq!(range.weight(&Combo::new(card!("JH"), card!("JC"))), 0.0);
+    assert_eq!(range.weighted_len(), 4.0);
+
+    let error = |s: &str| s.parse::<Range>().unwrap_err();
+    assert_eq!(error("AK, ZZ").position, 4);
+    assert_eq!(error("AK, AQx").position, 6);
+    assert_eq!(error("AKs:2").position, 4);
+    assert_eq!(error("TT-AKs").position, 3);
+    assert_eq!(error("AKs-QJs").position, 4);
+    assert_eq!(error("AK, Aé").position, 5);
+    assert_eq!(error("AK, AKsé").position, 7);
+    assert_eq!(error("AhAh").reason, "Duplicate card");
+}
+
+#[test]
+fn range_to_string() {
+    use range::Range;
+
+    let round_trip = |s: &str| s.parse::<Range>().unwrap().to_string();
+    assert_eq!(round_trip("TT+"), "TT+");
+    assert_eq!(round_trip("77-TT"), "TT-77");
+    assert_eq!(round_trip("AKs, AKo"), "AK");
+    assert_eq!(round_trip("A2s+, KQo"), "A2s+, KQo");
+    assert_eq!(round_trip("ATs-A8s, A5s"), "ATs-A8s, A5s");
+    assert_eq!(round_trip("QQ:0.5, KK+, AhKh"), "KK+, QQ:0.5, AhKh");
+    assert_eq!(round_trip("AT+"), "AT+");
+    assert_eq!(round_trip(""), "");
+
+    // Writing a range and reading it back gives the same range
+    let range = "22+, A2s+, KTs+, QJo:0.5, 7h6h, 5c4c:0.75"
+        .parse::<Range>()
+        .unwrap();
+    assert_eq!(range.to_string().parse::<Range>().unwrap(), range);
+}
+
+#[test]
+fn hand_parse() {
+    let hand = Hand::parse("AS KD 7h, 10c;\tQ♥\n").unwrap();
+    assert_eq!(
+        hand.cards(),
+        &[
+            card!("AS"),
+            card!("KD"),
+            card!("7H"),
+            card!("TC"),
+            card!("QH")
+        ]
+    );
+    assert!(Hand::parse("").unwrap().is_empty());
+    assert!(Hand::parse(" , ").unwrap().is_empty());
+
+    let errors = Hand::parse("AS, ZS KD 7x").err().unwrap();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].index, 1);
+    assert_eq!(errors[0].position, 4);
+    assert_eq!(errors[0].token, "ZS");
+    assert_eq!(errors[0].reason, "Invalid rank");
+    assert_eq!(errors[1].index, 3);
+    assert_eq!(errors[1].position, 11);
+    assert_eq!(errors[1].reason, "Invalid suit");
+
+    // Positions are byte offsets, after separators and cards of more than one byte
+    let errors = Hand::parse("Q♥\u{3000}ZS").err().unwrap();
+    assert_eq!(errors[0].position, 7);
+    assert_eq!(&"Q♥\u{3000}ZS"[errors[0].position..], "ZS");
+
+    // Duplicates are allowed unless they are rejected
+    assert_eq!(Hand::parse("AS as").unwrap().len(), 2);
+    let errors = Hand::parse_with("AS KD as", Duplicates::Reject)
+        .err()
+        .unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].index, 2);
+    assert_eq!(errors[0].reason, "Duplicate card");
+}
+
+#[test]
+fn card_and_hand_macros() {
+    const QUEEN_OF_HEARTS: Card = card!("QH");
+    assert_eq!(QUEEN_OF_HEARTS, Card::new(Rank::Queen, Suit::Hearts));
+
+    // Anything other than a literal is parsed when it runs
+    let s = String::from("TD");
+    assert_eq!(card!(s.as_str()), Card::new(Rank::Ten, Suit::Diamonds));
+    assert_eq!(hand!("AS", s.as_str()).cards(), &[card!("AS"), card!("TD")]);
+
+    // Literals follow the same rules as `Card::from_str`
+    assert_eq!(card!("SA"), card!("AS"));
+    assert_eq!(card!("1S"), card!("AS"));
+    assert_eq!(hand!("S1", "DT").cards(), &[card!("AS"), card!("TD")]);
+
+    assert_eq!(hand!("AS", "AS").len(), 2);
+    assert_eq!(
+        unique_hand!("AS", "KD").cards(),
+        &[card!("AS"), card!("KD")]
+    );
+    assert!(hand!().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Not a known card ZZ")]
+fn card_macro_panics_at_runtime() {
+    let s = "ZZ";
+    let _ = card!(s);
+}
+
+#[test]
+fn unicode_glyphs() {
+    assert_eq!(card!("AS").to_unicode_glyph(), Some('🂡'));
+    assert_eq!(card!("TH").to_unicode_glyph(), Some('🂺'));
+    assert_eq!(card!("JD").to_unicode_glyph(), Some('🃋'));
+    assert_eq!(card!("QC").to_unicode_glyph(), Some('🃝'));
+    assert_eq!(card!("KS").to_unicode_glyph(), Some('🂮'));
+    for card in Card::all_cards() {
+        assert_eq!(
+            Card::from_unicode_glyph(card.to_unicode_glyph().unwrap()),
+            Ok(*card)
+        );
+    }
+    // The Knight is not part of a standard deck
+    assert!(Card::from_unicode_glyph('🂬').is_err());
+    assert!(Card::from_unicode_glyph('A').is_err());
+
+    let red = Joker { color: Color::Red };
+    let black = Joker {
+        color: Color::Black,
+    };
+    assert_eq!(red.to_unicode_glyph(), Some('🂿'));
+    assert_eq!(black.to_unicode_glyph(), Some('🃏'));
+    assert_eq!(Joker::from_unicode_glyph('🂿'), Ok(red));
+    assert_eq!(Joker::from_unicode_glyph('🃏'), Ok(black));
+    assert_eq!(Joker::from_unicode_glyph('🃟'), Ok(Joker::default()));
+    assert!(Joker::from_unicode_glyph('🂡').is_err());
+    assert!(Card::from_unicode_glyph('🃏').is_err());
+}
+
+#[cfg(feature = "pretty")]
+#[test]
+fn art_cards() {
+    use art::{Art, Border, Face};
+
+    let art = Art::plain();
+    assert_eq!(
+        art.card(&card!("KS")),
+        "┌─────┐\n│K    │\n│  ♠  │\n│    K│\n└─────┘"
+    );
+    assert_eq!(art.back(), "┌─────┐\n│░░░░░│\n│░░░░░│\n│░░░░░│\n└─────┘");
+    assert_eq!(
+        art.faces(&[Face::Up(card!("TH")), Face::Up(card!("KS")), Face::Down]),
+        "┌───┌───┌─────┐\n│10 │K  │░░░░░│\n│  ♥│  ♠│░░░░░│\n│   │   │░░░░░│\n└───└───└─────┘"
+    );
+    assert_eq!(art.hand(&hand!()), "");
+
+    let ascii = Art {
+        border: Border::Ascii,
+        visible: 2,
+        ..art
+    };
+    assert_eq!(
+        ascii.hand(&hand!("2C", "3D")),
+        "+-+-----+\n|2|3    |\n| |  ♦  |\n| |    3|\n+-+-----+"
+    );
+    assert_eq!(
+        ascii.faces(&[Face::Joker(Joker::default())]),
+        "+-----+\n|JK   |\n|  ★  |\n|   JK|\n+-----+"
+    );
+
+    // Colors do not change what is drawn, only how
+    let colored = Art::default().card(&card!("AH"));
+    assert!(colored.contains('♥'));
+    assert_eq!(colored.lines().count(), 5);
+}
+
+#[cfg(feature = "svg")]
+#[test]
+fn svg_cards() {
+    let pips = |image: &str| image.matches("class=\"pip\"").count();
+    for card in Card::all_cards() {
+        let image = svg::card(card);
+        assert!(image.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(image.ends_with("</svg>"));
+        let expected = match card.rank {
+            Rank::Ace | Rank::Jack | Rank::Queen | Rank::King => 1,
+            rank => rank.ordinal() + 2,
+        };
+        assert_eq!(pips(&image), expected);
+    }
+    let queen = svg::card(&card!("QH"));
+    assert!(queen.contains("fill=\"#c8102e\""));
+    assert!(queen.contains(">Q</text>"));
+    assert!(svg::card(&card!("TS")).contains(">10</text>"));
+    assert!(svg::joker(&Joker::default()).contains(">JOKER</text>"));
+
+    let image = svg::hand(&hand!("AS", "2D", "3C"));
+    assert!(image.contains("width=\"340\" height=\"160\""));
+    assert_eq!(image.matches("<rect").count(), 3);
+
+    let image = svg::tableau(&[&[card!("AS")], &[card!("KD"), card!("QC")], &[]]);
+    assert!(image.contains("width=\"230\" height=\"460\""));
+    assert_eq!(image.matches("<rect").count(), 3);
+}
+
+#[test]
+fn locale_names() {
+    use locale::Locale;
+
+    assert_eq!(Locale::English.card_name(&card!("AS")), card!("AS").name());
+    assert_eq!(Locale::French.card_name(&card!("QH")), "Dame de Cœur");
+    assert_eq!(Locale::German.card_name(&card!("JC")), "Kreuz Bube");
+    assert_eq!(Locale::Spanish.card_name(&card!("KD")), "Rey de Diamantes");
+    assert_eq!(Locale::Italian.card_name(&card!("TS")), "Dieci di Picche");
+    assert_eq!(Locale::Dutch.card_name(&card!("AH")), "Harten Aas");
+
+    assert_eq!(Locale::German.card_abbreviation(&card!("KD")), "K♦");
+    assert_eq!(Locale::French.card_abbreviation(&card!("KD")), "R♦");
+    assert_eq!(Locale::French.card_abbreviation(&card!("TD")), "10♦");
+    assert_eq!(Locale::English.card_abbreviation(&card!("TD")), "T♦");
+
+    // Everything that is written can be read back
+    for locale in Locale::iterator() {
+        for card in Card::all_cards() {
+            assert_eq!(locale.parse_card(&locale.card_name(card)), Ok(*card));
+            assert_eq!(
+                locale.parse_card(&locale.card_abbreviation(card)),
+                Ok(*card)
+            );
+        }
+        for rank in Rank::iterator() {
+            assert_eq!(locale.parse_rank(locale.rank_name(*rank)), Ok(*rank));
+        }
+        for suit in Suit::iterator() {
+            assert_eq!(locale.parse_suit(locale.suit_name(*suit)), Ok(*suit));
+        }
+        assert_eq!(Locale::from_code(locale.code()), Ok(*locale));
+    }
+
+    assert_eq!(Locale::German.parse_rank("KÖNIG"), Ok(Rank::King));
+    assert_eq!(Locale::German.parse_card("b♠"), Ok(card!("JS")));
+    assert!(Locale::German.parse_card("J♠").is_err());
+    assert!(Locale::French.parse_card("♠").is_err());
+    assert!(Locale::French.parse_card("").is_err());
+    assert_eq!(Locale::from_code("fr-CA"), Ok(Locale::French));
+    assert_eq!(Locale::from_code("nl_BE"), Ok(Locale::Dutch));
+    assert!(Locale::from_code("pt").is_err());
+}
+
+#[cfg(feature = "pretty")]
+#[test]
+fn themes() {
+    use theme::Theme;
+
+    assert_eq!(Theme::Monochrome.pretty_card(&card!("AS")), "A♠");
+    assert_eq!(Theme::Monochrome.pretty_joker(&Joker::default()), "JK");
+    for suit in Suit::iterator() {
+        assert_eq!(Theme::Monochrome.suit_color(*suit), None);
+    }
+
+    let gray = Some(colored::Color::TrueColor {
+        r: 100,
+        g: 100,
+        b: 100,
+    });
+    let two = Theme::TwoColor;
+    assert_eq!(two.suit_color(Suit::Spades), gray);
+    assert_eq!(two.suit_color(Suit::Clubs), gray);
+    assert_eq!(two.suit_color(Suit::Hearts), Some(colored::Color::Red));
+    assert_eq!(two.suit_color(Suit::Diamonds), Some(colored::Color::Red));
+
+    let four = Theme::FourColor;
+    assert_eq!(four.suit_color(Suit::Spades), gray);
+    assert_eq!(four.suit_color(Suit::Hearts), Some(colored::Color::Red));
+    assert_eq!(four.suit_color(Suit::Diamonds), Some(colored::Color::Blue));
+    assert_eq!(four.suit_color(Suit::Clubs), Some(colored::Color::Green));
+
+    let high = Theme::HighContrast;
+    assert_eq!(
+        high.suit_color(Suit::Clubs),
+        Some(colored::Color::BrightWhite)
+    );
+    assert_eq!(
+        high.joker_color(Color::Red),
+        Some(colored::Color::BrightRed)
+    );
+
+    // The current theme is global, so it is not changed here where other tests could see it
+    assert_eq!(Theme::current(), Theme::from_env());
+    assert_eq!(Theme::iterator().len(), 4);
+    assert!(Theme::iterator().all(|t| Theme::iterator().filter(|u| *u == t).count() == 1));
+}
+
+#[cfg(feature = "wasm")]
+#[test]
+fn wasm_bindings() {
+    use wasm::{JsDeck, JsHand};
+
+    assert_eq!(wasm::parse_card("10h").ok(), Some("TH".to_string()));
+    assert_eq!(
+        wasm::card_name("A♠").ok(),
+        Some("Ace of Spades".to_string())
+    );
+    assert_eq!(wasm::card_glyph("AS").ok(), Some("🂡".to_string()));
+    assert_eq!(wasm::card_color("QD").ok(), Some("red".to_string()));
+
+    // Seeded shuffles match the Rust ones
+    let mut deck = JsDeck::new();
+    deck.seeded_shuffle(7);
+    let mut expected = Deck::new();
+    expected.seeded_shuffle(7);
+    assert_eq!(deck.deal(3), to_strings(&expected.deal(3)));
+    assert_eq!(deck.undealt_count(), 49);
+    assert_eq!(deck.dealt_count(), 3);
+    deck.reset();
+    assert_eq!(deck.cards().len(), 52);
+
+    let mut hand = JsHand::parse("AS 2S 3S, KD").ok().unwrap();
+    assert_eq!(hand.length(), 4);
+    assert_eq!(hand.deadwood().ok(), Some(10));
+    assert!(hand.push("kh").is_ok());
+    assert_eq!(hand.remove("AS").ok(), Some(true));
+    hand.sort();
+    assert_eq!(hand.to_string(), "2S 3S KH KD");
+}
+
+#[cfg(any(feature = "wasm", feature = "server"))]
+fn to_strings(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(|c| c.to_str()).collect()
+}
+
+#[cfg(feature = "python")]
+#[test]
+fn python_bindings() {
+    use pyo3::prelude::*;
+    use pyo3::types::PyModule;
+
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let module = PyModule::new_bound(py, "deckofcards").unwrap();
+        python::init(&module).unwrap();
+        // Pickling finds the classes by importing their module
+        let modules = py.import_bound("sys").unwrap().getattr("modules").unwrap();
+        modules.set_item("deckofcards", &module).unwrap();
+        let globals = module.dict();
+        py.run_bound(
+            r#"
+import pickle
+assert Card("10h") == Card("TH")
+assert sorted([Card("AS"), Card("2C"), Card("KH")]) == [Card("2C"), Card("KH"), Card("AS")]
+assert pickle.loads(pickle.dumps(Card("QD"))) == Card("QD")
+assert pickle.loads(pickle.dumps(Rank.Queen)) == Rank.Queen
+assert pickle.loads(pickle.dumps(Suit.Diamonds)) == Suit.Diamonds
+deck = Deck()
+deck.shuffle(42)
+order = list(deck)
+assert len(order) == 52 and deck.deal_one() == order[0]
+copy = pickle.loads(pickle.dumps(deck))
+assert list(copy) == order[1:] and copy.dealt_cards == [order[0]]
+hand = Hand(["AS", "KD"])
+hand.push(Card.from_rank_suit(Rank.Two, Suit.Clubs))
+hand.sort()
+assert str(hand) == "2C KD AS" and hand[-1] == Card("AS") and Card("KD") in hand
+assert list(pickle.loads(pickle.dumps(hand))) == list(hand)
+"#,
+            Some(&globals),
+            None,
+        )
+        .unwrap();
+    });
+}
+
+#[cfg(feature = "capi")]
+#[test]
+fn test_capi() {
+    use core::ffi::c_char;
+    unsafe {
+        let deck = capi::deckofcards_deck_new();
+        let hand = capi::deckofcards_hand_new();
+        capi::deckofcards_deck_seeded_shuffle(deck, 42);
+        assert_eq!(capi::deckofcards_deck_deal_to_hand(deck, hand, 5), 5);
+        assert_eq!(capi::deckofcards_deck_undealt_count(deck), 47);
+
+        // Same order as dealing from a Rust deck shuffled with the same seed
+        let mut expected = Deck::new();
+        expected.seeded_shuffle(42);
+        let mut expected = Hand::from_cards(&expected.deal(5));
+        expected.sort_descending_rank_suit();
+        assert!(capi::deckofcards_hand_sort(
+            hand,
+            capi::SortOrder::DescendingRankSuit as u32
+        ));
+        assert!(!capi::deckofcards_hand_sort(hand, 4));
+        let mut cards = [0u8; 8];
+        assert_eq!(
+            capi::deckofcards_hand_cards(hand, cards.as_mut_ptr(), cards.len()),
+            5
+        );
+        let ordinals = expected
+            .cards()
+            .iter()
+            .map(|c| c.ordinal() as u8)
+            .collect::<Vec<_>>();
+        assert_eq!(&cards[..5], &ordinals[..]);
+
+        let mut s = [0 as c_char; 3];
+        assert!(capi::deckofcards_card_to_str(
+            card!("TH").ordinal() as u8,
+            s.as_mut_ptr()
+        ));
+        assert_eq!(s.map(|c| c as u8), *b"TH\0");
+        assert_eq!(
+            capi::deckofcards_card_from_str(c"10h".as_ptr()),
+            card!("TH").ordinal() as i32
+        );
+        assert_eq!(capi::deckofcards_card_from_str(c"ZZ".as_ptr()), -1);
+        assert!(!capi::deckofcards_hand_push(hand, 52));
+
+        capi::deckofcards_hand_free(hand);
+        capi::deckofcards_deck_free(deck);
+        assert_eq!(capi::deckofcards_deck_undealt_count(core::ptr::null()), 0);
+    }
+}
+
+#[cfg(feature = "capi")]
+#[test]
+fn capi_header_is_current() {
+    let dir = env!("CARGO_MANIFEST_DIR");
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", dir)).unwrap();
+    let mut header = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{}/src/capi.rs", dir))
+        .generate()
+        .unwrap()
+        .write(&mut header);
+    let expected = std::fs::read_to_string(format!("{}/include/deckofcards.h", dir)).unwrap();
+    assert!(
+        String::from_utf8(header).unwrap() == expected,
+        "include/deckofcards.h is out of date, regenerate it with cbindgen"
+    );
+}
+
+#[cfg(feature = "server")]
+#[test]
+fn server_hides_cards() {
+    use crate::hearts::Hearts;
+    use crate::server::Server;
+    use serde_json::{json, Value};
+
+    let mut server = Server::new(Hearts::deal(5));
+    let replies = server.handle(7, r#"{"type":"join","name":"Ann"}"#);
+    assert_eq!(replies[0], (7, json!({ "type": "joined", "seat": 0 })));
+    let replies = server.handle(8, r#"{"type":"join","seat":0}"#);
+    assert_eq!(replies[0].1["message"], "Seat is taken");
+    let _ = server.handle(8, r#"{"type":"join","name":"Bob","seat":1}"#);
+
+    // Ann sees her own cards and only the number of everyone else's
+    let state = server.view(Some(0));
+    let table = &server.game().table;
+    let own = to_strings(table.seats[0].hand.cards());
+    assert_eq!(state["hand"], json!(own));
+    assert_eq!(state["seats"][1]["player"], "Bob");
+    assert_eq!(state["seats"][1]["cards"], json!(vec![Value::Null; 13]));
+    assert!(state["rules"].get("seed").is_none());
+    let text = state.to_string();
+    for seat in 1..4 {
+        for card in table.seats[seat].hand.cards() {
+            assert!(!text.contains(&format!("\"{}\"", card.to_str())));
+        }
+    }
+
+    // Only the seat whose turn it is can act, and every seat hears about it
+    let _ = server.handle(9, r#"{"type":"join"}"#);
+    let _ = server.handle(10, r#"{"type":"join"}"#);
+    let leader = server.game().turn().seat;
+    let replies = server.handle(
+        7 + (leader + 1) % 4,
+        r#"{"type":"act","action":{"play":"2C"}}"#,
+    );
+    assert_eq!(replies[0].1["message"], "Not this seat's turn");
+    let replies = server.handle(7 + leader, r#"{"type":"act","action":{"play":"2C"}}"#);
+    let events = replies
+        .iter()
+        .filter(|(_, m)| m["type"] == "event")
+        .collect::<Vec<_>>();
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0].1["action"], json!({ "play": "2C" }));
+    assert_eq!(server.view(None)["areas"][1]["cards"], json!(["2C"]));
+    assert!(server.handle(1, "not json")[0].1["message"] == "Message is not JSON");
+}
+
+#[cfg(feature = "server")]
+#[test]
+fn server_over_tcp() {
+    use crate::blackjack::Blackjack;
+    use crate::server::{self, Server};
+    use serde_json::Value;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let _ = std::thread::spawn(move || server::serve(listener, Server::new(Blackjack::deal(3))));
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    let mut lines = BufReader::new(stream.try_clone().unwrap()).lines();
+    let mut read = || -> Value { serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap() };
+    writeln!(stream, r#"{{"type":"join","name":"Ann"}}"#).unwrap();
+    assert_eq!(read()["type"], "joined");
+    let state = read();
+    assert_eq!(state["seats"][0]["player"], "Ann");
+    if state["turn"]["phase"] == "Playing" {
+        // The dealer's hole card is hidden until the player stands
+        assert!(state["seats"][1]["cards"][1].is_null());
+        writeln!(stream, r#"{{"type":"act","action":"stand"}}"#).unwrap();
+        assert_eq!(read()["action"], "stand");
+        let state = read();
+        assert!(state["seats"][1]["cards"][1].is_string());
+        assert!(state["rules"]["outcome"].is_string());
+    }
+}
+
+#[cfg(feature = "mental")]
+#[test]
+fn mental_poker() {
+    use crate::mental::{self, Group, Player};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    let group = Group::test();
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut players = (0..3)
+        .map(|_| Player::new(&group, &mut rng))
+        .collect::<Vec<_>>();
+    let mut deck = group.encode(&Deck::new());
+    for player in players.iter_mut() {
+        deck = player.shuffle(&deck, &mut rng);
+    }
+    for player in players.iter_mut() {
+        deck = player.lock(&deck, &mut rng).unwrap();
+    }
+    assert!(players[0].lock(&deck, &mut rng).is_err());
+
+    // Deal two cards to each player, which only they can read
+    let mut dealt = Vec::new();
+    for (position, card) in deck.iter().enumerate().take(6) {
+        let to = position % 3;
+        let mut card = card.clone();
+        for (i, player) in players.iter().enumerate() {
+            if i != to {
+                card = player.unlock(position, &card).unwrap();
+            }
+        }
+        assert_eq!(
+            players[(to + 1) % 3].open(position, &card),
+            Err("Not a card")
+        );
+        dealt.push(players[to].open(position, &card).unwrap());
+    }
+
+    let keys = players.iter().map(|p| p.keys()).collect::<Vec<_>>();
+    let cards = mental::verify(&group, &deck, &keys).unwrap();
+    assert_eq!(&cards[..6], &dealt[..]);
+    let mut sorted = cards.clone();
+    sorted.sort();
+    let mut all = Card::all_cards().to_vec();
+    all.sort();
+    assert_eq!(sorted, all);
+
+    // A player who swaps one card for another is caught
+    let mut forged = deck.clone();
+    forged[1] = forged[0].clone();
+    let mut forged_keys = keys.clone();
+    for k in forged_keys.iter_mut() {
+        k[1] = k[0].clone();
+    }
+    assert_eq!(
+        mental::verify(&group, &forged, &forged_keys),
+        Err("Card appears twice")
+    );
+    assert_eq!(group.decode(&deck[0]), Err("Not a card"));
+
+    // So is one who leaves cards out of the deck
+    let short_keys = keys.iter().map(|k| k[1..].to_vec()).collect::<Vec<_>>();
+    assert_eq!(
+        mental::verify(&group, &deck[1..], &short_keys),
+        Err("Deck does not hold 52 cards")
+    );
+
+    // The real group is slow, so only one card goes through it
+    let standard = Group::standard();
+    let mut player = Player::new(&standard, &mut rng);
+    let deck = standard.encode(&Deck::from_cards(&[card!("QH")]));
+    let deck = player.shuffle(&deck, &mut rng);
+    let deck = player.lock(&deck, &mut rng).unwrap();
+    assert_eq!(player.open(0, &deck[0]), Ok(card!("QH")));
+}
+
+#[cfg(feature = "games")]
+#[test]
+fn game_info_sets() {
+    use crate::blackjack::{self, Blackjack, DEALER};
+    use crate::game::Zone;
+    use crate::hearts::{self, Hearts};
+    use crate::solitaire::{self, Klondike};
+
+    // Hearts shows the seat its hand and the trick, and the rest of the cards are unseen
+    let mut game = Hearts::deal(9);
+    let leader = game.turn().seat;
+    game.act(leader, &hearts::Action::Play(card!("2C")))
+        .unwrap();
+    let seat = (leader + 1) % 4;
+    let info = game.info_set(Some(seat), Card::all_cards());
+    assert_eq!(info.hand, game.table.seats[seat].hand.cards());
+    assert_eq!(info.visible(Zone::Board), vec![card!("2C")]);
+    assert_eq!(info.count(Zone::Hand(leader)), 12);
+    assert_eq!(info.hidden(Zone::Hand(leader)), 12);
+    assert!(info.visible(Zone::Hand(leader)).is_empty());
+    assert_eq!(info.unseen.len(), 52 - 13 - 1);
+    assert!(!info.unseen.contains(&card!("2C")));
+    for other in (0..4).filter(|s| *s != seat) {
+        for card in game.table.seats[other].hand.cards() {
+            assert!(info.unseen.contains(card));
+        }
+    }
+    let spectator = game.info_set(None, Card::all_cards());
+    assert!(spectator.hand.is_empty());
+    assert_eq!(spectator.unseen.len(), 51);
+
+    // The dealer's hole card is unseen until the round is over
+    let mut game = Blackjack::deal(4);
+    while game.turn().phase != blackjack::Phase::Playing {
+        game.act(0, &blackjack::Action::Deal).unwrap();
+    }
+    let info = game.info_set(Some(0), Card::all_cards());
+    assert_eq!(info.hidden(Zone::Hand(DEALER)), 1);
+    let hole = game.table.seats[DEALER].hand.cards()[1];
+    assert!(info.unseen.contains(&hole));
+    game.act(0, &blackjack::Action::Stand).unwrap();
+    let info = game.info_set(Some(0), Card::all_cards());
+    assert_eq!(info.hidden(Zone::Hand(DEALER)), 0);
+    assert!(!info.unseen.contains(&hole));
+
+    // Klondike hides the face down tableau cards along with the stock
+    let mut deck = Deck::new();
+    deck.seeded_shuffle(2);
+    let game = Klondike::deal(deck);
+    let info = game.info_set(Some(0), Card::all_cards());
+    assert_eq!(info.hidden(solitaire::TABLEAU[6]), 6);
+    assert_eq!(info.stock, 24);
+    assert_eq!(info.unseen.len(), 24 + 21);
+}
+
+#[cfg(feature = "games")]
+#[test]
+fn determinize_deals() {
+    use crate::determinize::{Constraint, Sampler};
+    use crate::game::Zone;
+    use crate::hearts::{self, Hearts};
+    use rand::SeedableRng;
+    use rand_pcg::Pcg32;
+
+    let mut game = Hearts::deal(11);
+    let leader = game.turn().seat;
+    game.act(leader, &hearts::Action::Play(card!("2C")))
+        .unwrap();
+    let seat = (leader + 1) % 4;
+    let info = game.info_set(Some(seat), Card::all_cards());
+    let others = (0..4).filter(|s| *s != seat).collect::<Vec<_>>();
+    let held = info.unseen[0];
+    let lacked = info.unseen[1];
+    let mut sampler = Sampler::new(&info);
+    sampler
+        .constrain(Constraint::Void(Zone::Hand(others[0]), Suit::Hearts))
+        .constrain(Constraint::Holds(Zone::Hand(others[1]), held))
+        .constrain(Constraint::Lacks(Zone::Hand(others[2]), lacked));
+
+    let mut rng = Pcg32::seed_from_u64(5);
+    for _ in 0..100 {
+        let deal = sampler.sample(&mut rng).unwrap();
+        let mut dealt = Vec::new();
+        for other in &others {
+            let cards = deal.cards(Zone::Hand(*other));
+            assert_eq!(cards.len(), info.hidden(Zone::Hand(*other)));
+            dealt.extend_from_slice(cards);
+        }
+        assert!(deal
+            .cards(Zone::Hand(others[0]))
+            .iter()
+            .all(|c| c.suit != Suit::Hearts));
+        assert!(deal.cards(Zone::Hand(others[1])).contains(&held));
+        assert!(!deal.cards(Zone::Hand(others[2])).contains(&lacked));
+        dealt.sort();
+        let mut unseen = info.unseen.clone();
+        unseen.sort();
+        assert_eq!(dealt, unseen);
+
+        // The determinized game keeps what the seat knows and can be played on
+        let mut determinized = game.clone();
+        deal.apply(&mut determinized, &info).unwrap();
+        assert_eq!(determinized.info_set(Some(seat), Card::all_cards()), info);
+        let plays = determinized.rules.legal_plays(&determinized.table, seat);
+        assert!(determinized
+            .act(seat, &hearts::Action::Play(plays[0]))
+            .is_ok());
+    }
+
+    // Without constraints a card is as likely to be in any hand
+    let sampler = Sampler::new(&info);
+    let card = info.unseen[5];
+    let count = (0..3000)
+        .filter(|_| {
+            let deal = sampler.sample(&mut rng).unwrap();
+            deal.cards(Zone::Hand(others[0])).contains(&card)
+        })
+        .count();
+    let expected = 3000 * info.hidden(Zone::Hand(others[0])) / info.unseen.len();
+    assert!(count.abs_diff(expected) < 150);
+
+    // Constraints which contradict each other or what the seat can see are errors
+    let mut sampler = Sampler::new(&info);
+    sampler.constrain(Constraint::Holds(Zone::Hand(others[0]), info.hand[0]));
+    assert_eq!(sampler.sample(&mut rng), Err("Held card is not unseen"));
+    let mut sampler = Sampler::new(&info);
+    sampler
+        .constrain(Constraint::Holds(Zone::Hand(others[0]), held))
+        .constrain(Constraint::Lacks(Zone::Hand(others[0]), held));
+    assert_eq!(
+        sampler.sample(&mut rng),
+        Err("Constraints contradict each other")
+    );
+
+    // Deals which do not fit the places the seat cannot see are not applied
+    let mut sampler = Sampler::new(&info);
+    let hidden = info.hidden(Zone::Hand(others[0]));
+    sampler.constrain(Constraint::Count(Zone::Hand(others[0]), hidden - 1));
+    let deal = sampler.sample(&mut rng).unwrap();
+    let mut determinized = game.clone();
+    assert_eq!(
+        deal.apply(&mut determinized, &info),
+        Err("Deal does not fit the game")
+    );
+    let mut sampler = Sampler::new(&info);
+    sampler.constrain(Constraint::Count(Zone::Area(7), 0));
+    let deal = sampler.sample(&mut rng).unwrap();
+    assert_eq!(deal.apply(&mut determinized, &info), Err("No such zone"));
+
+    // Tight constraints are met by dealing the most constrained cards first
+    let mut sampler = Sampler::new(&info);
+    sampler
+        .attempts(20)
+        .constrain(Constraint::Void(Zone::Hand(others[0]), Suit::Hearts))
+        .constrain(Constraint::Void(Zone::Hand(others[0]), Suit::Spades))
+        .constrain(Constraint::Void(Zone::Hand(others[1]), Suit::Hearts))
+        .constrain(Constraint::Void(Zone::Hand(others[1]), Suit::Clubs));
+    let deal = sampler.sample(&mut rng).unwrap();
+    assert!(deal.cards(Zone::Hand(others[2])).len() == info.hidden(Zone::Hand(others[2])));
+    assert!(deal
+        .cards(Zone::Hand(others[0]))
+        .iter()
+        .all(|c| c.suit != Suit::Hearts && c.suit != Suit::Spades));
+
+    // Weighing deals makes the likely ones more common
+    let mut sampler = Sampler::new(&info);
+    let hand = Zone::Hand(others[0]);
+    sampler.weigh(move |deal| {
+        if deal.cards(hand).contains(&card) {
+            1.0
+        } else {
+            0.1
+        }
+    });
+    let count = (0..300)
+        .filter(|_| {
+            sampler
+                .sample(&mut rng)
+                .unwrap()
+                .cards(hand)
+                .contains(&card)
+        })
+        .count();
+    assert!(count > 150);
+
+    // Deals into the stock replace its undealt cards and keep the cards it dealt
+    let blackjack = crate::blackjack::Blackjack::deal(4);
+    let stock = blackjack.info_set(Some(0), Card::all_cards());
+    let deal = Sampler::new(&stock).sample(&mut rng).unwrap();
+    let mut determinized = blackjack.clone();
+    deal.apply(&mut determinized, &stock).unwrap();
+    assert!(!determinized.table.deck.dealt_cards.is_empty());
+    assert_eq!(
+        determinized.table.deck.dealt_cards,
+        blackjack.table.deck.dealt_cards
+    );
+    assert_eq!(determinized.table.deck.cards(), deal.cards(Zone::Stock));
+}
+
+#[cfg(all(feature = "std", feature = "games"))]
+#[test]
+fn mcts_search() {
+    use crate::blackjack::{self, Blackjack, PLAYER};
+    use crate::hearts::{self, Hearts};
+    use crate::mcts::{Mcts, Playable};
+    use rand::SeedableRng;
+    use rand_pcg::Pcg32;
+
+    // Players take 1 to 3 from a pile and whoever takes the last one wins
+    #[derive(Clone)]
+    struct Nim {
+        pile: usize,
+        turn: usize,
+    }
+
+    impl Playable for Nim {
+        type Action = usize;
+
+        fn players(&self) -> usize {
+            2
+        }
+
+        fn to_move(&self) -> Option<usize> {
+            (self.pile > 0).then_some(self.turn)
+        }
+
+        fn legal_actions(&self) -> Vec<usize> {
+            (1..=self.pile.min(3)).collect()
+        }
+
+        fn apply(&mut self, take: &usize) {
+            self.pile -= take;
+            self.turn = 1 - self.turn;
+        }
+
+        fn rewards(&self) -> Vec<f64> {
+            // The player to move has nothing left to take and has lost
+            let mut rewards = vec![1.0; 2];
+            rewards[self.turn] = 0.0;
+            rewards
+        }
+    }
+
+    let search = Mcts {
+        iterations: 3000,
+        ..Mcts::default()
+    };
+    assert_eq!(search.search(&Nim { pile: 10, turn: 0 }), Some(2));
+    assert_eq!(search.search(&Nim { pile: 7, turn: 1 }), Some(3));
+    assert_eq!(search.search(&Nim { pile: 0, turn: 0 }), None);
+    let statistics = search.evaluate(&Nim { pile: 10, turn: 0 });
+    assert_eq!(statistics.iter().map(|s| s.visits).sum::<usize>(), 3000);
+    assert_eq!(statistics, search.evaluate(&Nim { pile: 10, turn: 0 }));
+
+    // Blackjack stands on 20 and hits on 9 to 11
+    let search = Mcts {
+        iterations: 400,
+        ..Mcts::default()
+    };
+    let (mut stood, mut hit) = (false, false);
+    let mut game = Blackjack::deal(8);
+    for _ in 0..200 {
+        if game.turn().phase == blackjack::Phase::Playing {
+            let (total, _) = blackjack::total(game.table.seats[PLAYER].hand.cards());
+            if total == 20 {
+                assert_eq!(search.search(&game), Some(blackjack::Action::Stand));
+                stood = true;
+            } else if (9..=11).contains(&total) {
+                assert_eq!(search.search(&game), Some(blackjack::Action::Hit));
+                hit = true;
+            }
+            game.act(PLAYER, &blackjack::Action::Stand).unwrap();
+        }
+        game.act(PLAYER, &blackjack::Action::Deal).unwrap();
+    }
+    assert!(stood && hit);
+
+    // Hearts searches deals of the cards the seat cannot see and picks a legal card
+    let game = Hearts::deal(6);
+    let search = Mcts {
+        iterations: 200,
+        seed: 3,
+        ..Mcts::default()
+    };
+    let statistics = search.evaluate(&game);
+    assert!(statistics
+        .iter()
+        .all(|s| game.legal_actions().contains(&s.action)));
+    assert!(statistics.iter().all(|s| (0.0..=1.0).contains(&s.reward)));
+    assert_eq!(search.search(&game), Some(statistics[0].action));
+
+    // Deals keep the suits a player showed out of this hand out of their hand
+    let mut game = Hearts::deal(6);
+    let shown_out = |game: &game::Game<Hearts>| {
+        let seat = game.turn().seat;
+        (0..hearts::SEATS).any(|p| p != seat && !game.rules.voids[p].is_empty())
+    };
+    while !shown_out(&game) {
+        let seat = game.turn().seat;
+        let plays = game.rules.legal_plays(&game.table, seat);
+        game.act(seat, &hearts::Action::Play(plays[0])).unwrap();
+    }
+    let seat = game.turn().seat;
+    let mut rng = Pcg32::seed_from_u64(2);
+    for _ in 0..20 {
+        let deal = game.determinize(seat, &mut rng).unwrap();
+        for player in (0..hearts::SEATS).filter(|p| *p != seat) {
+            let hand = deal.table.seats[player].hand.cards();
+            assert!(game.rules.voids[player]
+                .iter()
+                .all(|suit| hand.iter().all(|c| c.suit != *suit)));
+        }
+    }
+
+    // Cards played to tricks are seen by everyone, so deals leave them where they are
+    let mut game = Hearts::deal(9);
+    while game.rules.last_trick.is_empty() || game.rules.trick.len() < 2 {
+        let seat = game.turn().seat;
+        let plays = game.rules.legal_plays(&game.table, seat);
+        game.act(seat, &hearts::Action::Play(plays[0])).unwrap();
+    }
+    let seat = game.turn().seat;
+    let info = game.info_set(Some(seat), Card::all_cards());
+    assert_eq!(
+        info.unseen.len(),
+        52 - 4 - 2 - game.table.seats[seat].hand.len()
+    );
+    for _ in 0..20 {
+        let deal = game.determinize(seat, &mut rng).unwrap();
+        for zone in hearts::TAKEN.iter().chain(&[game::Zone::Board]) {
+            assert_eq!(deal.table.cards(*zone), game.table.cards(*zone));
+        }
+        assert_eq!(
+            Hearts::hand_points(&deal.table),
+            Hearts::hand_points(&game.table)
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Color themes for pretty output.
+//!
+//! `to_pretty()` on a `Card` or `Joker` colors it with the current theme. Unless `Theme::set` is
+//! called first, the theme is read from the environment the first time it is used, so colors are
+//! off when the `NO_COLOR` environment variable is set.
+
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use colored::Colorize;
+
+use super::*;
+
+/// The theme used by `to_pretty()`, stored as its ordinal, or `UNSET` until it is first used
+static CURRENT: AtomicU8 = AtomicU8::new(UNSET);
+
+const UNSET: u8 = u8::MAX;
+
+/// How suits and jokers are colored
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Theme {
+    /// Red hearts and diamonds, gray spades and clubs
+    #[default]
+    TwoColor,
+    /// Red hearts, blue diamonds, green clubs and gray spades, as used by online poker rooms
+    FourColor,
+    /// No colors at all
+    Monochrome,
+    /// Bold bright red and bright white for dark terminals and poor eyesight
+    HighContrast,
+}
+
+impl Theme {
+    /// Returns an iterator through the themes
+    pub fn iterator() -> core::slice::Iter<'static, Theme> {
+        static THEMES: [Theme; 4] = [
+            Theme::TwoColor,
+            Theme::FourColor,
+            Theme::Monochrome,
+            Theme::HighContrast,
+        ];
+        THEMES.iter()
+    }
+
+    /// Returns the theme used by `to_pretty()`, which is `Theme::from_env()` unless it was set
+    pub fn current() -> Theme {
+        let mut ordinal = CURRENT.load(Ordering::Relaxed);
+        if ordinal == UNSET {
+            let theme = Theme::from_env().ordinal();
+            // Another thread may have set the theme in the meantime, and that one wins
+            ordinal = match CURRENT.compare_exchange(
+                UNSET,
+                theme,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => theme,
+                Err(current) => current,
+            };
+        }
+        *Theme::iterator()
+            .nth(ordinal as usize)
+            .unwrap_or(&Theme::TwoColor)
+    }
+
+    /// Sets the theme used by `to_pretty()`
+    pub fn set(theme: Theme) {
+        CURRENT.store(theme.ordinal(), Ordering::Relaxed);
+    }
+
+    fn ordinal(&self) -> u8 {
+        Theme::iterator().position(|t| t == self).unwrap_or(0) as u8
+    }
+
+    /// Returns `Monochrome` if the `NO_COLOR` environment variable is set to anything other than
+    /// an empty string, otherwise the default theme
+    pub fn from_env() -> Theme {
+        match std::env::var_os("NO_COLOR") {
+            Some(value) if !value.is_empty() => Theme::Monochrome,
+            _ => Theme::default(),
+        }
+    }
+
+    /// Returns the color the suit is drawn in, or `None` if it is not colored
+    pub fn suit_color(&self, suit: Suit) -> Option<colored::Color> {
+        match (self, suit) {
+            (Theme::Monochrome, _) => None,
+            (Theme::FourColor, Suit::Diamonds) => Some(colored::Color::Blue),
+            (Theme::FourColor, Suit::Clubs) => Some(colored::Color::Green),
+            (theme, suit) => theme.joker_color(suit.to_color()),
+        }
+    }
+
+    /// Returns the color a joker of the color is drawn in, or `None` if it is not colored
+    pub fn joker_color(&self, color: Color) -> Option<colored::Color> {
+        match (self, color) {
+            (Theme::Monochrome, _) => None,
+            (Theme::HighContrast, Color::Red) => Some(colored::Color::BrightRed),
+            (Theme::HighContrast, Color::Black) => Some(colored::Color::BrightWhite),
+            (_, Color::Red) => Some(colored::Color::Red),
+            (_, Color::Black) => Some(colored::Color::TrueColor {
+                r: GRAY.r,
+                g: GRAY.g,
+                b: GRAY.b,
+            }),
+        }
+    }
+
+    /// Draws the text in the color, in bold for the high contrast theme
+    pub fn paint(&self, text: &str, color: Option<colored::Color>) -> String {
+        match color {
+            Some(color) if *self == Theme::HighContrast => text.color(color).bold().to_string(),
+            Some(color) => text.color(color).to_string(),
+            None => text.to_string(),
+        }
+    }
+
+    /// Returns the rank and colored suit symbol of the card, e.g. "A♠"
+    pub fn pretty_card(&self, card: &Card) -> String {
+        format!(
+            "{}{}",
+            card.rank.to_char(),
+            self.paint(
+                &card.suit.to_unicode().to_string(),
+                self.suit_color(card.suit)
+            )
+        )
+    }
+
+    /// Returns "JK" colored for the joker
+    pub fn pretty_joker(&self, joker: &Joker) -> String {
+        self.paint("JK", self.joker_color(joker.color))
+    }
+}
@@ -0,0 +1,214 @@
+//! JavaScript bindings made with wasm-bindgen.
+//!
+//! Cards cross into JavaScript as their short strings, e.g. "AS", and are parsed leniently on the
+//! way in, so "10h" and "A♠" are accepted too. The README describes how to build the JavaScript
+//! module together with its TypeScript definitions.
+//!
+//! ```text
+//! import { Deck, Hand } from "deckofcards";
+//!
+//! const deck = new Deck();
+//! deck.seededShuffle(42n);
+//! const hand = Hand.parse(deck.deal(10).join(" "));
+//! console.log(hand.toString(), hand.deadwood());
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use super::*;
+
+/// Parses a card written in any notation `notation::parse_card` accepts
+fn to_card(s: &str) -> Result<Card, String> {
+    notation::parse_card(s, notation::Strictness::Lenient).map_err(|e| e.to_string())
+}
+
+fn to_strings(cards: &[Card]) -> Vec<String> {
+    cards.iter().map(|c| c.to_str()).collect()
+}
+
+/// Parses a card and returns its short string, e.g. "10h" returns "TH"
+#[wasm_bindgen(js_name = parseCard)]
+pub fn parse_card(s: &str) -> Result<String, JsError> {
+    Ok(to_card(s).map_err(|e| JsError::new(&e))?.to_str())
+}
+
+/// Returns the English name of a card, e.g. "Ace of Spades"
+#[wasm_bindgen(js_name = cardName)]
+pub fn card_name(s: &str) -> Result<String, JsError> {
+    Ok(to_card(s).map_err(|e| JsError::new(&e))?.name())
+}
+
+/// Returns the character for a card from the Unicode Playing Cards block
+#[wasm_bindgen(js_name = cardGlyph)]
+pub fn card_glyph(s: &str) -> Result<String, JsError> {
+    Ok(to_card(s)
+        .map_err(|e| JsError::new(&e))?
+        .to_unicode_glyph()
+        .map(String::from)
+        .unwrap_or_default())
+}
+
+/// Returns "red" or "black" for a card
+#[wasm_bindgen(js_name = cardColor)]
+pub fn card_color(s: &str) -> Result<String, JsError> {
+    let color = match to_card(s).map_err(|e| JsError::new(&e))?.suit.to_color() {
+        Color::Red => "red",
+        Color::Black => "black",
+    };
+    Ok(color.to_string())
+}
+
+/// A deck of cards for JavaScript, see `Deck`
+#[wasm_bindgen(js_name = Deck)]
+pub struct JsDeck {
+    deck: Deck<Card>,
+}
+
+#[wasm_bindgen(js_class = Deck)]
+impl JsDeck {
+    /// Creates a standard deck of 52 cards
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> JsDeck {
+        JsDeck { deck: Deck::new() }
+    }
+
+    /// Creates a deck from the cards, from the bottom to the top
+    #[wasm_bindgen(js_name = fromCards)]
+    pub fn from_cards(cards: Vec<String>) -> Result<JsDeck, JsError> {
+        let cards = cards
+            .iter()
+            .map(|c| to_card(c))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| JsError::new(&e))?;
+        Ok(JsDeck {
+            deck: Deck::from_cards(&cards),
+        })
+    }
+
+    /// Shuffles the undealt cards into a random order
+    pub fn shuffle(&mut self) {
+        self.deck.shuffle();
+    }
+
+    /// Shuffles the undealt cards into the same order as `Cards::seeded_shuffle` in Rust
+    #[wasm_bindgen(js_name = seededShuffle)]
+    pub fn seeded_shuffle(&mut self, seed: u64) {
+        self.deck.seeded_shuffle(seed);
+    }
+
+    /// Deals the top card
+    #[wasm_bindgen(js_name = dealOne)]
+    pub fn deal_one(&mut self) -> Result<String, JsError> {
+        Ok(self.deck.deal_one().map_err(JsError::new)?.to_str())
+    }
+
+    /// Deals up to the number of cards from the top
+    pub fn deal(&mut self, count: usize) -> Vec<String> {
+        to_strings(&self.deck.deal(count))
+    }
+
+    /// Puts the dealt cards back on top of the deck, so they are dealt again in the same order
+    pub fn reset(&mut self) {
+        self.deck.reset();
+    }
+
+    /// Returns the undealt cards from the bottom to the top
+    pub fn cards(&self) -> Vec<String> {
+        to_strings(self.deck.cards())
+    }
+
+    /// Returns the number of undealt cards
+    #[wasm_bindgen(getter, js_name = undealtCount)]
+    pub fn undealt_count(&self) -> usize {
+        self.deck.undealt_count()
+    }
+
+    /// Returns the number of dealt cards
+    #[wasm_bindgen(getter, js_name = dealtCount)]
+    pub fn dealt_count(&self) -> usize {
+        self.deck.dealt_count()
+    }
+}
+
+/// A hand of cards for JavaScript, see `Hand`
+#[wasm_bindgen(js_name = Hand)]
+pub struct JsHand {
+    hand: Hand,
+}
+
+#[wasm_bindgen(js_class = Hand)]
+impl JsHand {
+    /// Creates an empty hand
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> JsHand {
+        JsHand { hand: Hand::new() }
+    }
+
+    /// Parses cards separated by spaces or commas, see `Hand::parse`
+    pub fn parse(s: &str) -> Result<JsHand, JsError> {
+        Hand::parse(s)
+            .map(|hand| JsHand { hand })
+            .map_err(|errors| {
+                let errors = errors
+                    .iter()
+                    .map(|e| format!("{} at position {} ({})", e.reason, e.position, e.token))
+                    .collect::<Vec<_>>();
+                JsError::new(&errors.join(", "))
+            })
+    }
+
+    /// Adds a card
+    pub fn push(&mut self, card: &str) -> Result<(), JsError> {
+        self.hand
+            .push_card(to_card(card).map_err(|e| JsError::new(&e))?);
+        Ok(())
+    }
+
+    /// Removes the first matching card, returning false if there is none
+    pub fn remove(&mut self, card: &str) -> Result<bool, JsError> {
+        let card = to_card(card).map_err(|e| JsError::new(&e))?;
+        Ok(self.hand.remove_card(&card))
+    }
+
+    /// Removes every card
+    pub fn clear(&mut self) {
+        self.hand.clear();
+    }
+
+    /// Shuffles the cards into a random order
+    pub fn shuffle(&mut self) {
+        self.hand.shuffle();
+    }
+
+    /// Sorts the cards by suit and then by rank
+    pub fn sort(&mut self) {
+        self.hand.sort_suit_ascending_rank();
+    }
+
+    /// Returns the cards in order
+    pub fn cards(&self) -> Vec<String> {
+        to_strings(self.hand.cards())
+    }
+
+    /// Returns the number of cards
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.hand.len()
+    }
+
+    /// Returns the deadwood of the best gin rummy melds, see `rummy::best_melds`
+    pub fn deadwood(&self) -> Result<usize, JsError> {
+        Ok(rummy::best_melds(&self.hand)
+            .map_err(JsError::new)?
+            .deadwood_value())
+    }
+
+    /// Returns the cards as short strings separated by spaces
+    #[wasm_bindgen(js_name = toString)]
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.cards().join(" ")
+    }
+}
@@ -0,0 +1,63 @@
+//! Tests for the `deck` command, run as a separate process with its output piped.
+
+use std::process::{Command, Output};
+
+fn deck(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_deck"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn deck_deal() {
+    let output = deck(&["--seed", "42", "deal", "4", "5"]);
+    assert!(output.status.success());
+    let text = stdout(&output);
+    assert_eq!(text.lines().count(), 4);
+    assert!(text.starts_with("1: "));
+    // Piped output has no colors
+    assert!(!text.contains('\u{1b}'));
+    assert_eq!(stdout(&deck(&["--seed", "42", "deal", "4", "5"])), text);
+}
+
+#[test]
+fn deck_json() {
+    let output = deck(&["--json", "eval", "AS", "KS", "QS", "JS", "TS"]);
+    assert!(output.status.success());
+    assert_eq!(
+        stdout(&output).trim(),
+        r#"{"cards":["AS","KS","QS","JS","TS"],"category":"Straight Flush","ranks":["A"]}"#
+    );
+
+    let output = deck(&["--json", "convert", "name", "10h", "A♠"]);
+    assert_eq!(
+        stdout(&output).trim(),
+        r#"["Ten of Hearts","Ace of Spades"]"#
+    );
+
+    let output = deck(&["--json", "deal", "2", "2"]);
+    let text = stdout(&output);
+    assert!(text.contains(r#""undealt":48"#));
+}
+
+#[test]
+fn deck_errors() {
+    let output = deck(&["--json", "parse", "ZZ"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).starts_with(r#"{"error":"#));
+
+    let output = deck(&["deal", "9", "9"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap().trim(),
+        "deck: Cannot deal 81 cards from 52"
+    );
+
+    let output = deck(&["frobnicate"]);
+    assert_eq!(output.status.code(), Some(2));
+}